@@ -0,0 +1,233 @@
+//! Receive and change addresses of accounts.
+use bech32::{ToBase32, Variant};
+use bitcoin::secp256k1::Secp256k1;
+use bitcoin::util::bip32::{ChildNumber, DerivationPath, ExtendedPubKey};
+use bitcoin::{Address, Network, PublicKey};
+
+use crate::taproot::output_key;
+use crate::{derive_script_xpubs_from_seed, Error, ScriptType};
+
+/// Chain of an account's addresses.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Chain {
+    /// Receive addresses at `/0/index`.
+    Receive,
+    /// Change addresses at `/1/index`.
+    Change,
+}
+
+impl Chain {
+    /// Returns the child number of the chain, 0 for receive and 1 for change addresses.
+    pub fn index(&self) -> u32 {
+        match self {
+            Chain::Receive => 0,
+            Chain::Change => 1,
+        }
+    }
+}
+
+/// Address derived from an account.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DerivedAddress {
+    /// Derivation path of the address.
+    pub path: DerivationPath,
+    /// Public key at `path`, which is the internal key for taproot addresses.
+    pub public_key: PublicKey,
+    /// The address itself.
+    pub address: String,
+}
+
+/// Derives addresses of `script_type` on `chain` with an index range of `[start, end)` from an account `xpub` for `network`.
+/// Paths of the addresses are appended to `account_path`, which is the derivation path of `xpub`.
+/// Use [DerivationPath::master] if the path of `xpub` is unknown.
+pub fn derive_addresses(
+    xpub: &ExtendedPubKey,
+    account_path: &DerivationPath,
+    script_type: &ScriptType,
+    chain: Chain,
+    (start, end): (u32, u32),
+    network: Network,
+) -> Result<Vec<DerivedAddress>, Error> {
+    let secp = Secp256k1::verification_only();
+    let chain_number = ChildNumber::from_normal_idx(chain.index())?;
+    let chain_xpub = xpub.ckd_pub(&secp, chain_number)?;
+    let chain_path = account_path.child(chain_number);
+
+    let mut result = vec![];
+    for index in start..end {
+        let child_number = ChildNumber::from_normal_idx(index)?;
+        let public_key = chain_xpub.ckd_pub(&secp, child_number)?.public_key;
+        result.push(DerivedAddress {
+            path: chain_path.child(child_number),
+            address: address(&public_key, script_type, network)?,
+            public_key,
+        });
+    }
+
+    Ok(result)
+}
+
+/// Derives addresses of `script_type` on `chain` with an index range of `[start, end)` from the account of a `seed` at `account`.
+/// The optional bip39 `passphrase` is applied to `seed` before deriving.
+/// Accounts are derived like in [derive_script_xpubs_from_seed].
+pub fn derive_addresses_from_seed<S>(
+    seed: S,
+    passphrase: Option<&str>,
+    account: u32,
+    script_type: &ScriptType,
+    chain: Chain,
+    (start, end): (u32, u32),
+    network: Network,
+) -> Result<Vec<DerivedAddress>, Error>
+where
+    S: AsRef<str>,
+{
+    let end_account = account.checked_add(1).ok_or(Error::Bip32)?;
+    let xpubs = derive_script_xpubs_from_seed(
+        seed,
+        passphrase,
+        (account, end_account),
+        script_type,
+        network,
+    )?;
+    let (account_path, xpub) = xpubs.first().ok_or(Error::Bip32)?;
+
+    derive_addresses(
+        xpub,
+        account_path,
+        script_type,
+        chain,
+        (start, end),
+        network,
+    )
+}
+
+/// Returns the address of `script_type` for `public_key` on `network`.
+fn address(
+    public_key: &PublicKey,
+    script_type: &ScriptType,
+    network: Network,
+) -> Result<String, Error> {
+    // Derived keys are always compressed, so segwit addresses can't fail
+    let address = match script_type {
+        ScriptType::Pkh => Address::p2pkh(public_key, network),
+        ScriptType::ShWpkh => Address::p2shwpkh(public_key, network).map_err(|_| Error::Bip32)?,
+        ScriptType::Wpkh => Address::p2wpkh(public_key, network).map_err(|_| Error::Bip32)?,
+        ScriptType::Tr => return taproot_address(public_key, network),
+    };
+
+    Ok(address.to_string())
+}
+
+/// Returns the bech32m encoded P2TR address of an internal `public_key` on `network`.
+fn taproot_address(public_key: &PublicKey, network: Network) -> Result<String, Error> {
+    let hrp = match network {
+        Network::Bitcoin => "bc",
+        Network::Testnet | Network::Signet => "tb",
+        Network::Regtest => "bcrt",
+    };
+    // Witness version 1 followed by the x-only output key
+    let output_key = output_key(&public_key.key)?;
+    let mut data = vec![bech32::u5::try_from_u8(1).map_err(|_| Error::Bip32)?];
+    data.extend(output_key.serialize().to_base32());
+
+    bech32::encode(hrp, data, Variant::Bech32m).map_err(|_| Error::Bip32)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use bitcoin::util::bip32::{DerivationPath, ExtendedPubKey};
+    use bitcoin::Network;
+
+    use crate::address::{derive_addresses, derive_addresses_from_seed, Chain};
+    use crate::ScriptType;
+
+    const SEED: &str = "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about";
+
+    #[test]
+    fn derive_addresses_from_seed_derives_addresses() {
+        let script_types = [
+            (ScriptType::Pkh, "1LqBGSKuX5yYUonjxT5qGfpUsXKYYWeabA"),
+            (ScriptType::ShWpkh, "37VucYSaXLCAsxYyAPfbSi9eh4iEcbShgf"),
+            (
+                ScriptType::Wpkh,
+                "bc1qcr8te4kr609gcawutmrza0j4xv80jy8z306fyu",
+            ),
+            (
+                ScriptType::Tr,
+                "bc1p5cyxnuxmeuwuvkwfem96lqzszd02n6xdcjrs20cac6yqjjwudpxqkedrcr",
+            ),
+        ];
+
+        for (script_type, expected) in script_types.iter() {
+            let result = derive_addresses_from_seed(
+                SEED,
+                None,
+                0,
+                script_type,
+                Chain::Receive,
+                (0, 1),
+                Network::Bitcoin,
+            )
+            .unwrap();
+            assert_eq!(result[0].address, *expected);
+        }
+    }
+
+    #[test]
+    fn derive_addresses_from_seed_derives_change_addresses() {
+        let result = derive_addresses_from_seed(
+            SEED,
+            None,
+            0,
+            &ScriptType::Wpkh,
+            Chain::Change,
+            (0, 2),
+            Network::Bitcoin,
+        )
+        .unwrap();
+        assert_eq!(result.len(), 2);
+        assert_eq!(result[0].path.to_string(), "m/84'/0'/0'/1/0");
+        assert_eq!(
+            result[0].address,
+            "bc1q8c6fshw2dlwun7ekn9qwf37cu2rn755upcp6el"
+        );
+        assert_eq!(
+            result[0].public_key.to_string(),
+            "03025324888e429ab8e3dbaf1f7802648b9cd01e9b418485c5fa4c1b9b5700e1a6"
+        );
+        assert_eq!(result[1].path.to_string(), "m/84'/0'/0'/1/1");
+    }
+
+    #[test]
+    fn derive_addresses_derives_addresses_from_xpub() {
+        let xpub = ExtendedPubKey::from_str("xpub6CatWdiZiodmUeTDp8LT5or8nmbKNcuyvz7WyksVFkKB4RHwCD3XyuvPEbvqAQY3rAPshWcMLoP2fMFMKHPJ4ZeZXYVUhLv1VMrjPC7PW6V").unwrap();
+        let result = derive_addresses(
+            &xpub,
+            &DerivationPath::master(),
+            &ScriptType::Wpkh,
+            Chain::Receive,
+            (1, 2),
+            Network::Bitcoin,
+        )
+        .unwrap();
+        assert_eq!(result[0].path.to_string(), "m/0/1");
+        assert_eq!(
+            result[0].address,
+            "bc1qnjg0jd8228aq7egyzacy8cys3knf9xvrerkf9g"
+        );
+
+        let result = derive_addresses(
+            &xpub,
+            &DerivationPath::master(),
+            &ScriptType::Wpkh,
+            Chain::Receive,
+            (0, 1),
+            Network::Regtest,
+        )
+        .unwrap();
+        assert!(result[0].address.starts_with("bcrt1q"));
+    }
+}
@@ -0,0 +1,305 @@
+//! Bip85 applications besides bip39 child seeds.
+use std::str::FromStr;
+
+use bitcoin::secp256k1::Secp256k1;
+use bitcoin::util::bip32::{ChildNumber, DerivationPath, ExtendedPrivKey};
+use bitcoin::PrivateKey;
+use sha3::digest::{ExtendableOutput, Update, XofReader};
+use sha3::Shake256;
+
+use crate::Error;
+
+/// Bip85 application number for base64 passwords.
+const BIP85_PWD_BASE64_APPLICATION: u32 = 707764;
+/// Bip85 application number for base85 passwords.
+const BIP85_PWD_BASE85_APPLICATION: u32 = 707785;
+/// Bip85 application number for dice rolls.
+const BIP85_DICE_APPLICATION: u32 = 89101;
+/// Characters of base85 as specified by RFC 1924.
+const BASE85_CHARSET: &[u8] =
+    b"0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz!#$%&()*+-;<=>?@^_`{|}~";
+
+/// Encoding of bip85 passwords.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PasswordEncoding {
+    /// Base64 passwords with 20 to 86 characters.
+    Base64,
+    /// Base85 passwords with 10 to 80 characters.
+    Base85,
+}
+
+impl PasswordEncoding {
+    /// Returns the bip85 application number of `self`.
+    fn application(&self) -> u32 {
+        match self {
+            PasswordEncoding::Base64 => BIP85_PWD_BASE64_APPLICATION,
+            PasswordEncoding::Base85 => BIP85_PWD_BASE85_APPLICATION,
+        }
+    }
+
+    /// Returns the minimum and maximum length of passwords of `self`.
+    pub fn length_range(&self) -> (u32, u32) {
+        match self {
+            PasswordEncoding::Base64 => (20, 86),
+            PasswordEncoding::Base85 => (10, 80),
+        }
+    }
+
+    /// Encodes `entropy` with `self`.
+    fn encode(&self, entropy: &[u8]) -> String {
+        match self {
+            PasswordEncoding::Base64 => base64::encode(entropy),
+            PasswordEncoding::Base85 => entropy
+                .chunks(4)
+                .flat_map(|chunk| {
+                    let mut value = chunk
+                        .iter()
+                        .fold(0u32, |value, byte| (value << 8) | *byte as u32);
+                    let mut chars = [0u8; 5];
+                    for c in chars.iter_mut().rev() {
+                        *c = BASE85_CHARSET[(value % 85) as usize];
+                        value /= 85;
+                    }
+                    chars.to_vec()
+                })
+                .map(|c| c as char)
+                .collect(),
+        }
+    }
+}
+
+impl FromStr for PasswordEncoding {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "base64" => Ok(PasswordEncoding::Base64),
+            "base85" => Ok(PasswordEncoding::Base85),
+            _ => Err(Error::BadPasswordEncoding),
+        }
+    }
+}
+
+/// Derives hex child entropy with `length` bytes of a root `xprv` with an index range `[start, end)`.
+/// The `length` needs to be between 16 and 64 bytes.
+/// Returns a tuple of the index and its derived entropy.
+pub fn derive_child_hex(
+    xprv: &ExtendedPrivKey,
+    length: u32,
+    (start, end): (u32, u32),
+) -> Result<Vec<(u32, Vec<u8>)>, Error> {
+    let secp = Secp256k1::new();
+
+    (start..end)
+        .map(|i| Ok((i, bip85::to_hex(&secp, xprv, length, i)?)))
+        .collect()
+}
+
+/// Derives child WIF private keys of a root `xprv` with an index range `[start, end)`.
+/// The keys are compressed and for the network of `xprv`.
+/// Returns a tuple of the index and its derived key.
+pub fn derive_child_wifs(
+    xprv: &ExtendedPrivKey,
+    (start, end): (u32, u32),
+) -> Result<Vec<(u32, PrivateKey)>, Error> {
+    let secp = Secp256k1::new();
+
+    (start..end)
+        .map(|i| Ok((i, bip85::to_wif(&secp, xprv, i)?)))
+        .collect()
+}
+
+/// Derives child root xprvs of a root `xprv` with an index range `[start, end)`.
+/// The xprvs are for the network of `xprv`.
+/// Returns a tuple of the index and its derived xprv.
+pub fn derive_child_xprvs(
+    xprv: &ExtendedPrivKey,
+    (start, end): (u32, u32),
+) -> Result<Vec<(u32, ExtendedPrivKey)>, Error> {
+    let secp = Secp256k1::new();
+
+    (start..end)
+        .map(|i| Ok((i, bip85::to_xprv(&secp, xprv, i)?)))
+        .collect()
+}
+
+/// Derives child passwords with `length` characters in `encoding` of a root `xprv` with an index range `[start, end)`.
+/// The `length` needs to be within the [PasswordEncoding::length_range] of `encoding`.
+/// Returns a tuple of the index and its derived password.
+pub fn derive_child_passwords(
+    xprv: &ExtendedPrivKey,
+    length: u32,
+    encoding: &PasswordEncoding,
+    (start, end): (u32, u32),
+) -> Result<Vec<(u32, String)>, Error> {
+    let (min_length, max_length) = encoding.length_range();
+    if length < min_length || length > max_length {
+        return Err(Error::Bip85);
+    }
+    let secp = Secp256k1::new();
+
+    (start..end)
+        .map(|i| {
+            let path = DerivationPath::from(vec![
+                ChildNumber::from_hardened_idx(encoding.application())?,
+                ChildNumber::from_hardened_idx(length)?,
+                ChildNumber::from_hardened_idx(i).map_err(|_| Error::Bip85)?,
+            ]);
+            let entropy = bip85::derive(&secp, xprv, &path)?;
+            let password = encoding.encode(&entropy)[..length as usize].to_string();
+            Ok((i, password))
+        })
+        .collect()
+}
+
+/// Derives `rolls` rolls of a die with `sides` sides of a root `xprv` with an index range `[start, end)`.
+/// Rolls are zero-indexed and range from 0 to `sides - 1`. A die needs at least 2 sides and at least 1 roll.
+/// Returns a tuple of the index and its derived rolls.
+pub fn derive_child_dice(
+    xprv: &ExtendedPrivKey,
+    sides: u32,
+    rolls: u32,
+    (start, end): (u32, u32),
+) -> Result<Vec<(u32, Vec<u32>)>, Error> {
+    if sides < 2 || rolls < 1 {
+        return Err(Error::Bip85);
+    }
+    let secp = Secp256k1::new();
+
+    (start..end)
+        .map(|i| {
+            let path = DerivationPath::from(vec![
+                ChildNumber::from_hardened_idx(BIP85_DICE_APPLICATION)?,
+                ChildNumber::from_hardened_idx(sides).map_err(|_| Error::Bip85)?,
+                ChildNumber::from_hardened_idx(rolls).map_err(|_| Error::Bip85)?,
+                ChildNumber::from_hardened_idx(i).map_err(|_| Error::Bip85)?,
+            ]);
+            let entropy = bip85::derive(&secp, xprv, &path)?;
+            Ok((i, roll_dice(&entropy, sides, rolls)))
+        })
+        .collect()
+}
+
+/// Rolls a die with `sides` sides `rolls` times with the bip85 DRNG seeded by `entropy`.
+/// Trials with too many bits are trimmed to their most significant bits and skipped if they exceed `sides`.
+fn roll_dice(entropy: &[u8], sides: u32, rolls: u32) -> Vec<u32> {
+    let bits_per_roll = 32 - (sides - 1).leading_zeros();
+    let bytes_per_roll = bits_per_roll.div_ceil(8) as usize;
+    let mut shake = Shake256::default();
+    shake.update(entropy);
+    let mut drng = shake.finalize_xof();
+
+    let mut result = Vec::with_capacity(rolls as usize);
+    let mut trial = [0u8; 4];
+    while result.len() < rolls as usize {
+        drng.read(&mut trial[..bytes_per_roll]);
+        let value = trial[..bytes_per_roll]
+            .iter()
+            .fold(0u64, |value, byte| (value << 8) | *byte as u64)
+            >> (bytes_per_roll as u32 * 8 - bits_per_roll);
+        if value < sides as u64 {
+            result.push(value as u32);
+        }
+    }
+
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use bitcoin::util::bip32::ExtendedPrivKey;
+
+    use crate::applications::{
+        derive_child_dice, derive_child_hex, derive_child_passwords, derive_child_wifs,
+        derive_child_xprvs, PasswordEncoding,
+    };
+    use crate::Error;
+
+    // Root key of the bip85 test vectors
+    const ROOT: &str = "xprv9s21ZrQH143K2LBWUUQRFXhucrQqBpKdRRxNVq2zBqsx8HVqFk2uYo8kmbaLLHRdqtQpUm98uKfu3vca1LqdGhUtyoFnCNkfmXRyPXLjbKb";
+
+    #[test]
+    fn derive_child_hex_derives_entropy() {
+        let root = ExtendedPrivKey::from_str(ROOT).unwrap();
+        let result = derive_child_hex(&root, 64, (0, 2)).unwrap();
+        assert_eq!(result.len(), 2);
+        let hex: String = result[0].1.iter().map(|b| format!("{:02x}", b)).collect();
+        assert_eq!(hex, "492db4698cf3b73a5a24998aa3e9d7fa96275d85724a91e71aa2d645442f878555d078fd1f1f67e368976f04137b1f7a0d19232136ca50c44614af72b5582a5c");
+        assert_eq!(result[1].0, 1);
+
+        let result = derive_child_hex(&root, 16, (0, 1)).unwrap();
+        assert_eq!(result[0].1.len(), 16);
+        assert_eq!(derive_child_hex(&root, 15, (0, 1)), Err(Error::Bip85));
+        assert_eq!(derive_child_hex(&root, 65, (0, 1)), Err(Error::Bip85));
+        assert_eq!(derive_child_hex(&root, 16, (1, 0)), Ok(vec![]));
+    }
+
+    #[test]
+    fn derive_child_wifs_derives_keys() {
+        let root = ExtendedPrivKey::from_str(ROOT).unwrap();
+        let result = derive_child_wifs(&root, (0, 1)).unwrap();
+        assert_eq!(
+            result[0].1.to_wif(),
+            "Kzyv4uF39d4Jrw2W7UryTHwZr1zQVNk4dAFyqE6BuMrMh1Za7uhp"
+        );
+    }
+
+    #[test]
+    fn derive_child_xprvs_derives_xprvs() {
+        let root = ExtendedPrivKey::from_str(ROOT).unwrap();
+        let result = derive_child_xprvs(&root, (0, 1)).unwrap();
+        assert_eq!(result[0].1.to_string(), "xprv9s21ZrQH143K2srSbCSg4m4kLvPMzcWydgmKEnMmoZUurYuBuYG46c6P71UGXMzmriLzCCBvKQWBUv3vPB3m1SATMhp3uEjXHJ42jFg7myX");
+    }
+
+    #[test]
+    fn derive_child_passwords_derives_passwords() {
+        let root = ExtendedPrivKey::from_str(ROOT).unwrap();
+        let result = derive_child_passwords(&root, 21, &PasswordEncoding::Base64, (0, 1)).unwrap();
+        assert_eq!(result[0].1, "dKLoepugzdVJvdL56ogNV");
+
+        let result = derive_child_passwords(&root, 12, &PasswordEncoding::Base85, (0, 2)).unwrap();
+        assert_eq!(result[0].1, "_s`{TW89)i4`");
+        assert_eq!(result[1].0, 1);
+        assert_eq!(result[1].1.len(), 12);
+
+        let result = derive_child_passwords(&root, 86, &PasswordEncoding::Base64, (0, 1)).unwrap();
+        assert_eq!(result[0].1.len(), 86);
+        assert_eq!(
+            derive_child_passwords(&root, 19, &PasswordEncoding::Base64, (0, 1)),
+            Err(Error::Bip85)
+        );
+        assert_eq!(
+            derive_child_passwords(&root, 81, &PasswordEncoding::Base85, (0, 1)),
+            Err(Error::Bip85)
+        );
+
+        assert_eq!(
+            PasswordEncoding::from_str("base85"),
+            Ok(PasswordEncoding::Base85)
+        );
+        assert_eq!(
+            PasswordEncoding::from_str("base58"),
+            Err(Error::BadPasswordEncoding)
+        );
+    }
+
+    #[test]
+    fn derive_child_dice_derives_rolls() {
+        let root = ExtendedPrivKey::from_str(ROOT).unwrap();
+        let result = derive_child_dice(&root, 6, 10, (0, 2)).unwrap();
+        assert_eq!(result[0].1, vec![1, 0, 0, 2, 0, 1, 5, 5, 2, 4]);
+        assert_eq!(result[1].0, 1);
+        assert_eq!(result[1].1.len(), 10);
+
+        let result = derive_child_dice(&root, 1000, 3, (0, 1)).unwrap();
+        assert!(result[0].1.iter().all(|roll| *roll < 1000));
+        assert_eq!(derive_child_dice(&root, 1, 10, (0, 1)), Err(Error::Bip85));
+        assert_eq!(derive_child_dice(&root, 6, 0, (0, 1)), Err(Error::Bip85));
+        assert_eq!(
+            derive_child_dice(&root, 1 << 31, 10, (0, 1)),
+            Err(Error::Bip85)
+        );
+    }
+}
@@ -1,13 +1,37 @@
 use std::str::FromStr;
+use std::sync::atomic::{AtomicU64, Ordering};
 
-use bip85::bitcoin::util::bip32::{ExtendedPrivKey, ExtendedPubKey};
+use bip39::Language;
+use bip85::bitcoin::secp256k1::Secp256k1;
+use bip85::bitcoin::util::bip32::{ChildNumber, DerivationPath, ExtendedPrivKey};
+use bip85::bitcoin::Network;
 use clap::{App, Arg, ArgMatches};
-use seed_utils::WordCount;
+use rand::seq::SliceRandom;
+use seed_utils::{
+    AccountDescriptors, Chain, ChildRegistry, ChildTarget, EntropyEncoding, EntropySource,
+    ExtendedKey, LastWord, MixedEntropy, PasswordEncoding, PhysicalEntropy, PhysicalSource,
+    RecoveryTarget, RegistryEntry, ScriptType, VersionedString, WordCount,
+};
 use xyzpub::Version;
 
+const ADDRESSES_SUB: &str = "addresses";
 const CHILD_SUB: &str = "child";
+const CHILD_HEX_SUB: &str = "child-hex";
+const CHILD_WIF_SUB: &str = "child-wif";
+const CHILD_XPRV_SUB: &str = "child-xprv";
+const CONVERT_SUB: &str = "convert";
+const DESCRIPTOR_SUB: &str = "descriptor";
+const DICE_SUB: &str = "dice";
+const ENTROPY_SUB: &str = "entropy";
 const EXTEND_SUB: &str = "extend";
+const FIND_CHILD_SUB: &str = "find-child";
+const GENERATE_SUB: &str = "generate";
+const INSPECT_SUB: &str = "inspect";
+const LAST_WORD_SUB: &str = "last-word";
+const PASSWORD_SUB: &str = "password";
+const RECOVER_SUB: &str = "recover";
 const TRUNCATE_SUB: &str = "truncate";
+const VALIDATE_SUB: &str = "validate";
 const XOR_SUB: &str = "xor";
 const XPRV_SUB: &str = "xprv";
 const XPUB_SUB: &str = "xpub";
@@ -18,19 +42,820 @@ const NUMBER_ARG: &str = "number";
 const WORDS_ARG: &str = "words";
 const ROOT_ARG: &str = "root";
 const TYPE_ARG: &str = "type";
+const PASSPHRASE_ARG: &str = "passphrase";
+const PROMPT_PASSPHRASE_ARG: &str = "prompt-passphrase";
+const NETWORK_ARG: &str = "network";
+const LANGUAGE_ARG: &str = "language";
+const XPUB_ARG: &str = "xpub";
+const ENTROPY_ARG: &str = "entropy";
+const SOURCE_ARG: &str = "source";
+const MIX_ARG: &str = "mix";
+const RANDOM_ARG: &str = "random";
+const TARGET_ARG: &str = "target";
+const SWAPS_ARG: &str = "swaps";
+const THREADS_ARG: &str = "threads";
+const INPUT_ARG: &str = "input";
+const FROM_ARG: &str = "from";
+const SCRIPT_ARG: &str = "script";
+const OUTPUT_KEYS_ARG: &str = "output-keys";
+const PATH_ARG: &str = "path";
+const ACCOUNT_ARG: &str = "account";
+const CHANGE_ARG: &str = "change";
+const KEY_ARG: &str = "key";
+const BYTES_ARG: &str = "bytes";
+const LENGTH_ARG: &str = "length";
+const ENCODING_ARG: &str = "encoding";
+const SIDES_ARG: &str = "sides";
+const ROLLS_ARG: &str = "rolls";
+const LABEL_ARG: &str = "label";
+const REGISTRY_ARG: &str = "registry";
+const CHILD_ARG: &str = "child";
+const COUNT_ARG: &str = "count";
+
+/// Number of indexes derived at once by subcommands which derive whole ranges.
+const CHUNK_SIZE: u32 = 1000;
+
+const LANGUAGES: &[&str] = &[
+    "english",
+    "chinese-simplified",
+    "chinese-traditional",
+    "czech",
+    "french",
+    "italian",
+    "japanese",
+    "korean",
+    "portuguese",
+    "spanish",
+];
 
 fn main() -> Result<(), String> {
     let matches = App::new("seed-utils")
         .version("0.1.0")
         .about("CLI seed utilities")
         .subcommand(
-            App::new(CHILD_SUB)
-                .about("Derives a child seed from a seed")
+            App::new(ADDRESSES_SUB)
+                .about("Derives receive or change addresses of an account from a seed, a root xprv or an account xpub")
+                .arg(
+                    Arg::with_name(SEED_ARG)
+                        .help("Seed, root xprv, account xpub or account xprv to derive addresses from. Keys may have any SLIP-132 version")
+                        .index(1)
+                        .required(true),
+                )
+                .arg(
+                    Arg::with_name(LANGUAGE_ARG)
+                        .help("Language of the seed. Detected from its words if not set")
+                        .short("l")
+                        .long(LANGUAGE_ARG)
+                        .takes_value(true)
+                        .possible_values(LANGUAGES),
+                )
+                .arg(
+                    Arg::with_name(PASSPHRASE_ARG)
+                        .help("Bip39 passphrase of the seed")
+                        .short("p")
+                        .long(PASSPHRASE_ARG)
+                        .takes_value(true)
+                        .conflicts_with(PROMPT_PASSPHRASE_ARG),
+                )
+                .arg(
+                    Arg::with_name(PROMPT_PASSPHRASE_ARG)
+                        .help("Prompts for the bip39 passphrase of the seed")
+                        .short("P")
+                        .long(PROMPT_PASSPHRASE_ARG)
+                        .takes_value(false),
+                )
+                .arg(
+                    Arg::with_name(ACCOUNT_ARG)
+                        .help("Account index of the seed or root xprv to derive addresses at")
+                        .short("a")
+                        .long(ACCOUNT_ARG)
+                        .takes_value(true)
+                        .default_value("0"),
+                )
+                .arg(
+                    Arg::with_name(CHANGE_ARG)
+                        .help("Derives change instead of receive addresses")
+                        .short("c")
+                        .long(CHANGE_ARG)
+                        .takes_value(false),
+                )
+                .arg(
+                    Arg::with_name(INDEX_ARG)
+                        .help("Index of the first address")
+                        .short("i")
+                        .long(INDEX_ARG)
+                        .takes_value(true)
+                        .default_value("0"),
+                )
+                .arg(
+                    Arg::with_name(NUMBER_ARG)
+                        .help("Number of addresses to derive, starting from index")
+                        .short("n")
+                        .long(NUMBER_ARG)
+                        .takes_value(true)
+                        .default_value("10"),
+                )
+                .arg(
+                    Arg::with_name(SCRIPT_ARG)
+                        .help("Script type of the addresses")
+                        .short("s")
+                        .long(SCRIPT_ARG)
+                        .takes_value(true)
+                        .possible_values(&["pkh", "sh-wpkh", "wpkh", "tr"])
+                        .default_value("wpkh"),
+                )
+                .arg(
+                    Arg::with_name(NETWORK_ARG)
+                        .help("Network of the addresses. Testnets use coin type 1. Taken from the version of extended keys")
+                        .long(NETWORK_ARG)
+                        .takes_value(true)
+                        .possible_values(&["bitcoin", "testnet", "signet", "regtest"])
+                        .default_value("bitcoin"),
+                ),
+        )
+        .subcommand(
+            App::new(CHILD_SUB)
+                .about("Derives a child seed from a seed")
+                .arg(
+                    Arg::with_name(SEED_ARG)
+                        .help("Seed or xprv with any SLIP-132 version to derive from")
+                        .required(true),
+                )
+                .arg(
+                    Arg::with_name(LANGUAGE_ARG)
+                        .help("Language of the derived seeds, which is part of their bip85 derivation path. Defaults to english")
+                        .short("l")
+                        .long(LANGUAGE_ARG)
+                        .takes_value(true)
+                        .possible_values(LANGUAGES),
+                )
+                .arg(
+                    Arg::with_name(PASSPHRASE_ARG)
+                        .help("Bip39 passphrase of the seed")
+                        .short("p")
+                        .long(PASSPHRASE_ARG)
+                        .takes_value(true)
+                        .conflicts_with(PROMPT_PASSPHRASE_ARG),
+                )
+                .arg(
+                    Arg::with_name(PROMPT_PASSPHRASE_ARG)
+                        .help("Prompts for the bip39 passphrase of the seed")
+                        .short("P")
+                        .long(PROMPT_PASSPHRASE_ARG)
+                        .takes_value(false),
+                )
+                .arg(
+                    Arg::with_name(INDEX_ARG)
+                        .help("Index to derive at. An index path like 3/7/0 derives each child from the previous one and prints the whole lineage")
+                        .short("i")
+                        .long(INDEX_ARG)
+                        .takes_value(true)
+                        .default_value("0"),
+                )
+                .arg(
+                    Arg::with_name(NUMBER_ARG)
+                        .help("Number of seeds to derive, starting from index. Can't be used with index paths")
+                        .short("n")
+                        .long(NUMBER_ARG)
+                        .takes_value(true)
+                        .default_value("1"),
+                )
+                .arg(
+                    Arg::with_name(WORDS_ARG)
+                        .help("Number of words of the derived seed")
+                        .short("w")
+                        .long(WORDS_ARG)
+                        .takes_value(true)
+                        .possible_values(&["12", "15", "18", "21", "24"])
+                        .default_value("24"),
+                )
+                .arg(
+                    Arg::with_name(LABEL_ARG)
+                        .help("Label of the child seed in the registry. Unknown labels are registered at index or at the lowest free index")
+                        .long(LABEL_ARG)
+                        .takes_value(true)
+                        .requires(REGISTRY_ARG),
+                )
+                .arg(
+                    Arg::with_name(REGISTRY_ARG)
+                        .help("JSON file of labelled child seeds. Created if it doesn't exist")
+                        .long(REGISTRY_ARG)
+                        .takes_value(true),
+                ),
+        )
+        .subcommand(
+            App::new(CHILD_HEX_SUB)
+                .about("Derives bip85 hex entropy from a seed")
+                .arg(
+                    Arg::with_name(SEED_ARG)
+                        .help("Seed or xprv with any SLIP-132 version to derive from")
+                        .index(1)
+                        .required(true),
+                )
+                .arg(
+                    Arg::with_name(LANGUAGE_ARG)
+                        .help("Language of the seed. Detected from its words if not set")
+                        .short("l")
+                        .long(LANGUAGE_ARG)
+                        .takes_value(true)
+                        .possible_values(LANGUAGES),
+                )
+                .arg(
+                    Arg::with_name(PASSPHRASE_ARG)
+                        .help("Bip39 passphrase of the seed")
+                        .short("p")
+                        .long(PASSPHRASE_ARG)
+                        .takes_value(true)
+                        .conflicts_with(PROMPT_PASSPHRASE_ARG),
+                )
+                .arg(
+                    Arg::with_name(PROMPT_PASSPHRASE_ARG)
+                        .help("Prompts for the bip39 passphrase of the seed")
+                        .short("P")
+                        .long(PROMPT_PASSPHRASE_ARG)
+                        .takes_value(false),
+                )
+                .arg(
+                    Arg::with_name(INDEX_ARG)
+                        .help("Index to derive at")
+                        .short("i")
+                        .long(INDEX_ARG)
+                        .takes_value(true)
+                        .default_value("0"),
+                )
+                .arg(
+                    Arg::with_name(NUMBER_ARG)
+                        .help("Number of entropies to derive, starting from index")
+                        .short("n")
+                        .long(NUMBER_ARG)
+                        .takes_value(true)
+                        .default_value("1"),
+                )
+                .arg(
+                    Arg::with_name(BYTES_ARG)
+                        .help("Number of bytes of the derived entropy, between 16 and 64")
+                        .short("b")
+                        .long(BYTES_ARG)
+                        .takes_value(true)
+                        .default_value("64"),
+                ),
+        )
+        .subcommand(
+            App::new(CHILD_WIF_SUB)
+                .about("Derives bip85 WIF private keys from a seed")
+                .arg(
+                    Arg::with_name(SEED_ARG)
+                        .help("Seed or xprv with any SLIP-132 version to derive from")
+                        .index(1)
+                        .required(true),
+                )
+                .arg(
+                    Arg::with_name(LANGUAGE_ARG)
+                        .help("Language of the seed. Detected from its words if not set")
+                        .short("l")
+                        .long(LANGUAGE_ARG)
+                        .takes_value(true)
+                        .possible_values(LANGUAGES),
+                )
+                .arg(
+                    Arg::with_name(PASSPHRASE_ARG)
+                        .help("Bip39 passphrase of the seed")
+                        .short("p")
+                        .long(PASSPHRASE_ARG)
+                        .takes_value(true)
+                        .conflicts_with(PROMPT_PASSPHRASE_ARG),
+                )
+                .arg(
+                    Arg::with_name(PROMPT_PASSPHRASE_ARG)
+                        .help("Prompts for the bip39 passphrase of the seed")
+                        .short("P")
+                        .long(PROMPT_PASSPHRASE_ARG)
+                        .takes_value(false),
+                )
+                .arg(
+                    Arg::with_name(INDEX_ARG)
+                        .help("Index to derive at")
+                        .short("i")
+                        .long(INDEX_ARG)
+                        .takes_value(true)
+                        .default_value("0"),
+                )
+                .arg(
+                    Arg::with_name(NUMBER_ARG)
+                        .help("Number of keys to derive, starting from index")
+                        .short("n")
+                        .long(NUMBER_ARG)
+                        .takes_value(true)
+                        .default_value("1"),
+                )
+                .arg(
+                    Arg::with_name(NETWORK_ARG)
+                        .help("Network to encode the keys for. Taken from the version of xprvs")
+                        .long(NETWORK_ARG)
+                        .takes_value(true)
+                        .possible_values(&["bitcoin", "testnet", "signet", "regtest"])
+                        .default_value("bitcoin"),
+                ),
+        )
+        .subcommand(
+            App::new(CHILD_XPRV_SUB)
+                .about("Derives bip85 root xprvs from a seed")
+                .arg(
+                    Arg::with_name(SEED_ARG)
+                        .help("Seed or xprv with any SLIP-132 version to derive from")
+                        .index(1)
+                        .required(true),
+                )
+                .arg(
+                    Arg::with_name(LANGUAGE_ARG)
+                        .help("Language of the seed. Detected from its words if not set")
+                        .short("l")
+                        .long(LANGUAGE_ARG)
+                        .takes_value(true)
+                        .possible_values(LANGUAGES),
+                )
+                .arg(
+                    Arg::with_name(PASSPHRASE_ARG)
+                        .help("Bip39 passphrase of the seed")
+                        .short("p")
+                        .long(PASSPHRASE_ARG)
+                        .takes_value(true)
+                        .conflicts_with(PROMPT_PASSPHRASE_ARG),
+                )
+                .arg(
+                    Arg::with_name(PROMPT_PASSPHRASE_ARG)
+                        .help("Prompts for the bip39 passphrase of the seed")
+                        .short("P")
+                        .long(PROMPT_PASSPHRASE_ARG)
+                        .takes_value(false),
+                )
+                .arg(
+                    Arg::with_name(INDEX_ARG)
+                        .help("Index to derive at")
+                        .short("i")
+                        .long(INDEX_ARG)
+                        .takes_value(true)
+                        .default_value("0"),
+                )
+                .arg(
+                    Arg::with_name(NUMBER_ARG)
+                        .help("Number of xprvs to derive, starting from index")
+                        .short("n")
+                        .long(NUMBER_ARG)
+                        .takes_value(true)
+                        .default_value("1"),
+                )
+                .arg(
+                    Arg::with_name(NETWORK_ARG)
+                        .help("Network to encode the xprvs for. Taken from the version of xprvs")
+                        .long(NETWORK_ARG)
+                        .takes_value(true)
+                        .possible_values(&["bitcoin", "testnet", "signet", "regtest"])
+                        .default_value("bitcoin"),
+                ),
+        )
+        .subcommand(
+            App::new(CONVERT_SUB)
+                .about("Converts an extended key to another SLIP-132 version")
+                .arg(
+                    Arg::with_name(KEY_ARG)
+                        .help("Extended key with any SLIP-132 version to convert")
+                        .index(1)
+                        .required(true),
+                )
+                .arg(
+                    Arg::with_name(TYPE_ARG)
+                        .help("Version to convert the key to. Needs to be private for xprvs and public for xpubs")
+                        .short("t")
+                        .long(TYPE_ARG)
+                        .takes_value(true)
+                        .required(true)
+                        .possible_values(&[
+                            "xpub", "ypub", "zpub", "tpub", "upub", "vpub", "Ypub", "Zpub", "Upub",
+                            "Vpub", "xprv", "yprv", "zprv", "tprv", "uprv", "vprv", "Yprv", "Zprv",
+                            "Uprv", "Vprv",
+                        ]),
+                ),
+        )
+        .subcommand(
+            App::new(DESCRIPTOR_SUB)
+                .about("Derives receive, change and multipath output descriptors of accounts from a seed")
+                .arg(
+                    Arg::with_name(SEED_ARG)
+                        .help("Seed to derive descriptors from")
+                        .index(1)
+                        .required(true),
+                )
+                .arg(
+                    Arg::with_name(LANGUAGE_ARG)
+                        .help("Language of the seed. Detected from its words if not set")
+                        .short("l")
+                        .long(LANGUAGE_ARG)
+                        .takes_value(true)
+                        .possible_values(LANGUAGES),
+                )
+                .arg(
+                    Arg::with_name(PASSPHRASE_ARG)
+                        .help("Bip39 passphrase of the seed")
+                        .short("p")
+                        .long(PASSPHRASE_ARG)
+                        .takes_value(true)
+                        .conflicts_with(PROMPT_PASSPHRASE_ARG),
+                )
+                .arg(
+                    Arg::with_name(PROMPT_PASSPHRASE_ARG)
+                        .help("Prompts for the bip39 passphrase of the seed")
+                        .short("P")
+                        .long(PROMPT_PASSPHRASE_ARG)
+                        .takes_value(false),
+                )
+                .arg(
+                    Arg::with_name(INDEX_ARG)
+                        .help("Account index to derive descriptors at")
+                        .short("i")
+                        .long(INDEX_ARG)
+                        .takes_value(true)
+                        .default_value("0"),
+                )
+                .arg(
+                    Arg::with_name(NUMBER_ARG)
+                        .help("Number of accounts to derive descriptors for, starting from index")
+                        .short("n")
+                        .long(NUMBER_ARG)
+                        .takes_value(true)
+                        .default_value("1"),
+                )
+                .arg(
+                    Arg::with_name(SCRIPT_ARG)
+                        .help("Script type of the descriptors")
+                        .short("s")
+                        .long(SCRIPT_ARG)
+                        .takes_value(true)
+                        .possible_values(&["pkh", "sh-wpkh", "wpkh", "tr"])
+                        .default_value("wpkh"),
+                )
+                .arg(
+                    Arg::with_name(NETWORK_ARG)
+                        .help("Network to derive descriptors for. Testnets use coin type 1 and tpubs")
+                        .long(NETWORK_ARG)
+                        .takes_value(true)
+                        .possible_values(&["bitcoin", "testnet", "signet", "regtest"])
+                        .default_value("bitcoin"),
+                ),
+        )
+        .subcommand(
+            App::new(DICE_SUB)
+                .about("Derives bip85 dice rolls from a seed")
+                .arg(
+                    Arg::with_name(SEED_ARG)
+                        .help("Seed or xprv with any SLIP-132 version to derive from")
+                        .index(1)
+                        .required(true),
+                )
+                .arg(
+                    Arg::with_name(LANGUAGE_ARG)
+                        .help("Language of the seed. Detected from its words if not set")
+                        .short("l")
+                        .long(LANGUAGE_ARG)
+                        .takes_value(true)
+                        .possible_values(LANGUAGES),
+                )
+                .arg(
+                    Arg::with_name(PASSPHRASE_ARG)
+                        .help("Bip39 passphrase of the seed")
+                        .short("p")
+                        .long(PASSPHRASE_ARG)
+                        .takes_value(true)
+                        .conflicts_with(PROMPT_PASSPHRASE_ARG),
+                )
+                .arg(
+                    Arg::with_name(PROMPT_PASSPHRASE_ARG)
+                        .help("Prompts for the bip39 passphrase of the seed")
+                        .short("P")
+                        .long(PROMPT_PASSPHRASE_ARG)
+                        .takes_value(false),
+                )
+                .arg(
+                    Arg::with_name(INDEX_ARG)
+                        .help("Index to derive at")
+                        .short("i")
+                        .long(INDEX_ARG)
+                        .takes_value(true)
+                        .default_value("0"),
+                )
+                .arg(
+                    Arg::with_name(NUMBER_ARG)
+                        .help("Number of roll sequences to derive, starting from index")
+                        .short("n")
+                        .long(NUMBER_ARG)
+                        .takes_value(true)
+                        .default_value("1"),
+                )
+                .arg(
+                    Arg::with_name(SIDES_ARG)
+                        .help("Number of sides of the die, at least 2. Rolls range from 0 to sides - 1")
+                        .short("s")
+                        .long(SIDES_ARG)
+                        .takes_value(true)
+                        .default_value("6"),
+                )
+                .arg(
+                    Arg::with_name(ROLLS_ARG)
+                        .help("Number of rolls of each sequence, at least 1")
+                        .short("r")
+                        .long(ROLLS_ARG)
+                        .takes_value(true)
+                        .default_value("10"),
+                ),
+        )
+        .subcommand(
+            App::new(ENTROPY_SUB)
+                .about("Converts between a seed and its raw entropy as hex, binary or base64")
+                .arg(
+                    Arg::with_name(INPUT_ARG)
+                        .help("Seed or encoded entropy to convert")
+                        .index(1)
+                        .required(true),
+                )
+                .arg(
+                    Arg::with_name(FROM_ARG)
+                        .help("Format of the input")
+                        .short("f")
+                        .long(FROM_ARG)
+                        .takes_value(true)
+                        .possible_values(&["mnemonic", "hex", "binary", "base64"])
+                        .default_value("mnemonic"),
+                )
+                .arg(
+                    Arg::with_name(LANGUAGE_ARG)
+                        .help("Language of the printed seed. Defaults to the language of the input seed or english")
+                        .short("l")
+                        .long(LANGUAGE_ARG)
+                        .takes_value(true)
+                        .possible_values(LANGUAGES),
+                ),
+        )
+        .subcommand(
+            App::new(EXTEND_SUB)
+                .about("Creates a new seed by extending the entropy of a 12, 15, 18 or 21 word seed")
+                .arg(
+                    Arg::with_name(SEED_ARG)
+                        .help("Seed to extend")
+                        .index(1)
+                        .required(true),
+                )
+                .arg(
+                    Arg::with_name(LANGUAGE_ARG)
+                        .help("Language of the extended seed. Defaults to the language of the seed")
+                        .short("l")
+                        .long(LANGUAGE_ARG)
+                        .takes_value(true)
+                        .possible_values(LANGUAGES),
+                )
+                .arg(
+                    Arg::with_name(WORDS_ARG)
+                        .help("Number of words of the extended seed")
+                        .short("w")
+                        .long(WORDS_ARG)
+                        .takes_value(true)
+                        .possible_values(&["15", "18", "21", "24"])
+                        .default_value("24"),
+                )
+                .arg(
+                    Arg::with_name(ENTROPY_ARG)
+                        .help("Dice rolls, coin flips or cards to use as entropy for the added words instead of the OS random number generator")
+                        .short("e")
+                        .long(ENTROPY_ARG)
+                        .takes_value(true),
+                )
+                .arg(
+                    Arg::with_name(SOURCE_ARG)
+                        .help("Physical source of the entropy. Cards are written like AS, TD or 10D, 7H and KC")
+                        .long(SOURCE_ARG)
+                        .takes_value(true)
+                        .possible_values(&["d6", "d20", "coin", "cards"])
+                        .default_value("d6"),
+                )
+                .arg(
+                    Arg::with_name(MIX_ARG)
+                        .help("Mixes the physical entropy with entropy of the OS random number generator")
+                        .long(MIX_ARG)
+                        .takes_value(false)
+                        .requires(ENTROPY_ARG),
+                ),
+        )
+        .subcommand(
+            App::new(FIND_CHILD_SUB)
+                .about("Finds the bip85 index of a child seed by searching the children of a seed")
+                .arg(
+                    Arg::with_name(SEED_ARG)
+                        .help("Parent seed or xprv with any SLIP-132 version to search the children of")
+                        .index(1)
+                        .required(true),
+                )
+                .arg(
+                    Arg::with_name(CHILD_ARG)
+                        .help("Child seed, or the master fingerprint, root or account xpub, or one of the first 20 receive addresses of the child seed without passphrase")
+                        .index(2)
+                        .required(true),
+                )
+                .arg(
+                    Arg::with_name(LANGUAGE_ARG)
+                        .help("Language of the child seed if it is given by its keys. Defaults to english")
+                        .short("l")
+                        .long(LANGUAGE_ARG)
+                        .takes_value(true)
+                        .possible_values(LANGUAGES),
+                )
+                .arg(
+                    Arg::with_name(PASSPHRASE_ARG)
+                        .help("Bip39 passphrase of the parent seed")
+                        .short("p")
+                        .long(PASSPHRASE_ARG)
+                        .takes_value(true)
+                        .conflicts_with(PROMPT_PASSPHRASE_ARG),
+                )
+                .arg(
+                    Arg::with_name(PROMPT_PASSPHRASE_ARG)
+                        .help("Prompts for the bip39 passphrase of the parent seed")
+                        .short("P")
+                        .long(PROMPT_PASSPHRASE_ARG)
+                        .takes_value(false),
+                )
+                .arg(
+                    Arg::with_name(INDEX_ARG)
+                        .help("Index to start searching at")
+                        .short("i")
+                        .long(INDEX_ARG)
+                        .takes_value(true)
+                        .default_value("0"),
+                )
+                .arg(
+                    Arg::with_name(COUNT_ARG)
+                        .help("Number of indexes to search, starting from index. Keys are searched across all word counts")
+                        .short("c")
+                        .long(COUNT_ARG)
+                        .takes_value(true)
+                        .default_value("1000"),
+                )
+                .arg(
+                    Arg::with_name(THREADS_ARG)
+                        .help("Number of threads to use. Defaults to one thread per CPU")
+                        .long(THREADS_ARG)
+                        .takes_value(true)
+                        .default_value("0"),
+                ),
+        )
+        .subcommand(
+            App::new(GENERATE_SUB)
+                .about("Generates a new seed and prints it with its root fingerprint")
+                .arg(
+                    Arg::with_name(WORDS_ARG)
+                        .help("Number of words of the generated seed")
+                        .short("w")
+                        .long(WORDS_ARG)
+                        .takes_value(true)
+                        .possible_values(&["12", "15", "18", "21", "24"])
+                        .default_value("24"),
+                )
+                .arg(
+                    Arg::with_name(LANGUAGE_ARG)
+                        .help("Language of the generated seed")
+                        .short("l")
+                        .long(LANGUAGE_ARG)
+                        .takes_value(true)
+                        .possible_values(LANGUAGES)
+                        .default_value("english"),
+                )
+                .arg(
+                    Arg::with_name(PASSPHRASE_ARG)
+                        .help("Bip39 passphrase of the seed")
+                        .short("p")
+                        .long(PASSPHRASE_ARG)
+                        .takes_value(true)
+                        .conflicts_with(PROMPT_PASSPHRASE_ARG),
+                )
+                .arg(
+                    Arg::with_name(PROMPT_PASSPHRASE_ARG)
+                        .help("Prompts for the bip39 passphrase of the seed")
+                        .short("P")
+                        .long(PROMPT_PASSPHRASE_ARG)
+                        .takes_value(false),
+                )
+                .arg(
+                    Arg::with_name(XPUB_ARG)
+                        .help("Also prints the first account xpub of the given type")
+                        .short("x")
+                        .long(XPUB_ARG)
+                        .takes_value(true)
+                        .possible_values(&["xpub", "ypub", "zpub"]),
+                )
+                .arg(
+                    Arg::with_name(NETWORK_ARG)
+                        .help("Network to derive the xpub for. Testnets use coin type 1 and testnet versions")
+                        .long(NETWORK_ARG)
+                        .takes_value(true)
+                        .possible_values(&["bitcoin", "testnet", "signet", "regtest"])
+                        .default_value("bitcoin"),
+                )
+                .arg(
+                    Arg::with_name(ENTROPY_ARG)
+                        .help("Dice rolls, coin flips or cards to use as entropy instead of the OS random number generator")
+                        .short("e")
+                        .long(ENTROPY_ARG)
+                        .takes_value(true),
+                )
+                .arg(
+                    Arg::with_name(SOURCE_ARG)
+                        .help("Physical source of the entropy. Cards are written like AS, TD or 10D, 7H and KC")
+                        .long(SOURCE_ARG)
+                        .takes_value(true)
+                        .possible_values(&["d6", "d20", "coin", "cards"])
+                        .default_value("d6"),
+                )
+                .arg(
+                    Arg::with_name(MIX_ARG)
+                        .help("Mixes the physical entropy with entropy of the OS random number generator")
+                        .long(MIX_ARG)
+                        .takes_value(false)
+                        .requires(ENTROPY_ARG),
+                ),
+        )
+        .subcommand(
+            App::new(INSPECT_SUB)
+                .about("Decodes an extended key with any SLIP-132 version or a seed and prints what it is")
+                .arg(
+                    Arg::with_name(INPUT_ARG)
+                        .help("Extended key or seed to inspect")
+                        .index(1)
+                        .required(true),
+                )
+                .arg(
+                    Arg::with_name(LANGUAGE_ARG)
+                        .help("Language of the seed. Detected from its words if not set")
+                        .short("l")
+                        .long(LANGUAGE_ARG)
+                        .takes_value(true)
+                        .possible_values(LANGUAGES),
+                ),
+        )
+        .subcommand(
+            App::new(LAST_WORD_SUB)
+                .about("Lists every valid last word of a seed with 11, 14, 17, 20 or 23 words")
+                .arg(
+                    Arg::with_name(SEED_ARG)
+                        .help("Seed without its last word")
+                        .index(1)
+                        .required(true),
+                )
+                .arg(
+                    Arg::with_name(LANGUAGE_ARG)
+                        .help("Language of the seed. Defaults to the first language which contains all words")
+                        .short("l")
+                        .long(LANGUAGE_ARG)
+                        .takes_value(true)
+                        .possible_values(LANGUAGES),
+                )
+                .arg(
+                    Arg::with_name(RANDOM_ARG)
+                        .help("Picks one of the valid last words at random")
+                        .short("r")
+                        .long(RANDOM_ARG)
+                        .takes_value(false),
+                ),
+        )
+        .subcommand(
+            App::new(PASSWORD_SUB)
+                .about("Derives bip85 passwords from a seed")
                 .arg(
                     Arg::with_name(SEED_ARG)
-                        .help("Seed to derive")
+                        .help("Seed or xprv with any SLIP-132 version to derive from")
+                        .index(1)
                         .required(true),
                 )
+                .arg(
+                    Arg::with_name(LANGUAGE_ARG)
+                        .help("Language of the seed. Detected from its words if not set")
+                        .short("l")
+                        .long(LANGUAGE_ARG)
+                        .takes_value(true)
+                        .possible_values(LANGUAGES),
+                )
+                .arg(
+                    Arg::with_name(PASSPHRASE_ARG)
+                        .help("Bip39 passphrase of the seed")
+                        .short("p")
+                        .long(PASSPHRASE_ARG)
+                        .takes_value(true)
+                        .conflicts_with(PROMPT_PASSPHRASE_ARG),
+                )
+                .arg(
+                    Arg::with_name(PROMPT_PASSPHRASE_ARG)
+                        .help("Prompts for the bip39 passphrase of the seed")
+                        .short("P")
+                        .long(PROMPT_PASSPHRASE_ARG)
+                        .takes_value(false),
+                )
                 .arg(
                     Arg::with_name(INDEX_ARG)
                         .help("Index to derive at")
@@ -41,39 +866,82 @@ fn main() -> Result<(), String> {
                 )
                 .arg(
                     Arg::with_name(NUMBER_ARG)
-                        .help("Number of seeds to derive, starting from index")
+                        .help("Number of passwords to derive, starting from index")
                         .short("n")
                         .long(NUMBER_ARG)
                         .takes_value(true)
                         .default_value("1"),
                 )
                 .arg(
-                    Arg::with_name(WORDS_ARG)
-                        .help("Number of words of the derived seed")
-                        .short("w")
-                        .long(WORDS_ARG)
+                    Arg::with_name(LENGTH_ARG)
+                        .help("Number of characters of the derived passwords, between 20 and 86 for base64 and between 10 and 80 for base85")
+                        .short("L")
+                        .long(LENGTH_ARG)
                         .takes_value(true)
-                        .possible_values(&["12", "18", "24"])
-                        .default_value("24"),
+                        .default_value("21"),
+                )
+                .arg(
+                    Arg::with_name(ENCODING_ARG)
+                        .help("Encoding of the derived passwords")
+                        .short("e")
+                        .long(ENCODING_ARG)
+                        .takes_value(true)
+                        .possible_values(&["base64", "base85"])
+                        .default_value("base64"),
                 ),
         )
         .subcommand(
-            App::new(EXTEND_SUB)
-                .about("Creates a new seed by extending the entropy of a 12 or 18 word seed")
+            App::new(RECOVER_SUB)
+                .about("Recovers a damaged seed with unknown, misspelled or swapped words and lists all checksum valid candidates")
                 .arg(
                     Arg::with_name(SEED_ARG)
-                        .help("Seed to extend")
+                        .help("Damaged seed. Unknown words are written as ?")
                         .index(1)
                         .required(true),
                 )
                 .arg(
-                    Arg::with_name(WORDS_ARG)
-                        .help("Number of words of the extended seed")
-                        .short("w")
-                        .long(WORDS_ARG)
+                    Arg::with_name(LANGUAGE_ARG)
+                        .help("Language of the seed. Defaults to the language which contains most words")
+                        .short("l")
+                        .long(LANGUAGE_ARG)
                         .takes_value(true)
-                        .possible_values(&["18", "24"])
-                        .default_value("24"),
+                        .possible_values(LANGUAGES),
+                )
+                .arg(
+                    Arg::with_name(PASSPHRASE_ARG)
+                        .help("Bip39 passphrase of the seed, used to derive keys for the target")
+                        .short("p")
+                        .long(PASSPHRASE_ARG)
+                        .takes_value(true)
+                        .conflicts_with(PROMPT_PASSPHRASE_ARG),
+                )
+                .arg(
+                    Arg::with_name(PROMPT_PASSPHRASE_ARG)
+                        .help("Prompts for the bip39 passphrase of the seed")
+                        .short("P")
+                        .long(PROMPT_PASSPHRASE_ARG)
+                        .takes_value(false),
+                )
+                .arg(
+                    Arg::with_name(TARGET_ARG)
+                        .help("Master fingerprint, root or account xpub, or one of the first 20 receive addresses of the seed to narrow down the candidates")
+                        .short("t")
+                        .long(TARGET_ARG)
+                        .takes_value(true),
+                )
+                .arg(
+                    Arg::with_name(SWAPS_ARG)
+                        .help("Also tries to swap neighbouring words")
+                        .short("s")
+                        .long(SWAPS_ARG)
+                        .takes_value(false),
+                )
+                .arg(
+                    Arg::with_name(THREADS_ARG)
+                        .help("Number of threads to use. Defaults to one thread per CPU")
+                        .long(THREADS_ARG)
+                        .takes_value(true)
+                        .default_value("0"),
                 ),
         )
         .subcommand(
@@ -86,16 +954,57 @@ fn main() -> Result<(), String> {
                         .index(1)
                         .required(true),
                 )
+                .arg(
+                    Arg::with_name(LANGUAGE_ARG)
+                        .help("Language of the truncated seed. Defaults to the language of the seed")
+                        .short("l")
+                        .long(LANGUAGE_ARG)
+                        .takes_value(true)
+                        .possible_values(LANGUAGES),
+                )
                 .arg(
                     Arg::with_name(WORDS_ARG)
                         .help("Number of words of the truncated seed")
                         .short("w")
                         .long(WORDS_ARG)
                         .takes_value(true)
-                        .possible_values(&["12", "18"])
+                        .possible_values(&["12", "15", "18", "21"])
                         .default_value("12"),
                 ),
         )
+        .subcommand(
+            App::new(VALIDATE_SUB)
+                .about("Validates a seed and reports unknown words, checksum, word count, entropy and root fingerprint")
+                .arg(
+                    Arg::with_name(SEED_ARG)
+                        .help("Seed to validate")
+                        .index(1)
+                        .required(true),
+                )
+                .arg(
+                    Arg::with_name(LANGUAGE_ARG)
+                        .help("Language of the seed. Defaults to the language which contains most words")
+                        .short("l")
+                        .long(LANGUAGE_ARG)
+                        .takes_value(true)
+                        .possible_values(LANGUAGES),
+                )
+                .arg(
+                    Arg::with_name(PASSPHRASE_ARG)
+                        .help("Bip39 passphrase of the seed, used for the root fingerprint")
+                        .short("p")
+                        .long(PASSPHRASE_ARG)
+                        .takes_value(true)
+                        .conflicts_with(PROMPT_PASSPHRASE_ARG),
+                )
+                .arg(
+                    Arg::with_name(PROMPT_PASSPHRASE_ARG)
+                        .help("Prompts for the bip39 passphrase of the seed")
+                        .short("P")
+                        .long(PROMPT_PASSPHRASE_ARG)
+                        .takes_value(false),
+                ),
+        )
         .subcommand(
             App::new(XOR_SUB)
             .about("Does a XOR of multiple seeds")
@@ -105,6 +1014,14 @@ fn main() -> Result<(), String> {
                     .multiple(true)
                     .min_values(2)
                     .required(true),
+            )
+            .arg(
+                Arg::with_name(LANGUAGE_ARG)
+                    .help("Language of the XORed seed. Defaults to the language of the first seed")
+                    .short("l")
+                    .long(LANGUAGE_ARG)
+                    .takes_value(true)
+                    .possible_values(LANGUAGES),
             ),
         )
         .subcommand(
@@ -112,10 +1029,33 @@ fn main() -> Result<(), String> {
                 .about("Derives account or root xpubs from a seed")
                 .arg(
                     Arg::with_name(SEED_ARG)
-                        .help("Seed to derive xpubs from")
+                        .help("Seed or xprv or xpub with any SLIP-132 version to derive xpubs from. Accounts need a seed or root xprv and xpubs only derive non-hardened paths")
                         .index(1)
                         .required(true),
                 )
+                .arg(
+                    Arg::with_name(LANGUAGE_ARG)
+                        .help("Language of the seed. Detected from its words if not set")
+                        .short("l")
+                        .long(LANGUAGE_ARG)
+                        .takes_value(true)
+                        .possible_values(LANGUAGES),
+                )
+                .arg(
+                    Arg::with_name(PASSPHRASE_ARG)
+                        .help("Bip39 passphrase of the seed")
+                        .short("p")
+                        .long(PASSPHRASE_ARG)
+                        .takes_value(true)
+                        .conflicts_with(PROMPT_PASSPHRASE_ARG),
+                )
+                .arg(
+                    Arg::with_name(PROMPT_PASSPHRASE_ARG)
+                        .help("Prompts for the bip39 passphrase of the seed")
+                        .short("P")
+                        .long(PROMPT_PASSPHRASE_ARG)
+                        .takes_value(false),
+                )
                 .arg(
                     Arg::with_name(ROOT_ARG)
                         .help("Derives xpub at bip32 root instead of account level")
@@ -124,6 +1064,13 @@ fn main() -> Result<(), String> {
                         .takes_value(false)
                         .conflicts_with_all(&[INDEX_ARG, NUMBER_ARG]),
                 )
+                .arg(
+                    Arg::with_name(PATH_ARG)
+                        .help("Derivation path to derive xpubs at instead of account level, like m/0h or m/84h/0h/{0..5}h. The type only sets the encoding")
+                        .long(PATH_ARG)
+                        .takes_value(true)
+                        .conflicts_with_all(&[ROOT_ARG, INDEX_ARG, NUMBER_ARG]),
+                )
                 .arg(
                     Arg::with_name(INDEX_ARG)
                         .help("Index to derive xpub at")
@@ -142,12 +1089,27 @@ fn main() -> Result<(), String> {
                 )
                 .arg(
                     Arg::with_name(TYPE_ARG)
-                        .help("Type of xpub to return")
+                        .help("Type of xpub to return. Taproot (tr) xpubs are serialized as xpub")
                         .short("t")
                         .long(TYPE_ARG)
                         .takes_value(true)
-                        .possible_values(&["xpub", "ypub", "zpub"])
+                        .possible_values(&["xpub", "ypub", "zpub", "tr"])
                         .default_value("zpub"),
+                )
+                .arg(
+                    Arg::with_name(OUTPUT_KEYS_ARG)
+                        .help("Number of tweaked x-only receive output keys to derive per taproot account")
+                        .long(OUTPUT_KEYS_ARG)
+                        .takes_value(true)
+                        .default_value("5"),
+                )
+                .arg(
+                    Arg::with_name(NETWORK_ARG)
+                        .help("Network to derive xpubs for. Testnets use coin type 1 and testnet versions. Taken from the version of extended keys")
+                        .long(NETWORK_ARG)
+                        .takes_value(true)
+                        .possible_values(&["bitcoin", "testnet", "signet", "regtest"])
+                        .default_value("bitcoin"),
                 ),
         )
         .subcommand(
@@ -155,10 +1117,33 @@ fn main() -> Result<(), String> {
                 .about("Derives account or root xprvs from a seed")
                 .arg(
                     Arg::with_name(SEED_ARG)
-                        .help("Seed to derive xprvs from")
+                        .help("Seed or xprv with any SLIP-132 version to derive xprvs from. Accounts need a seed or root xprv")
                         .index(1)
                         .required(true),
                 )
+                .arg(
+                    Arg::with_name(LANGUAGE_ARG)
+                        .help("Language of the seed. Detected from its words if not set")
+                        .short("l")
+                        .long(LANGUAGE_ARG)
+                        .takes_value(true)
+                        .possible_values(LANGUAGES),
+                )
+                .arg(
+                    Arg::with_name(PASSPHRASE_ARG)
+                        .help("Bip39 passphrase of the seed")
+                        .short("p")
+                        .long(PASSPHRASE_ARG)
+                        .takes_value(true)
+                        .conflicts_with(PROMPT_PASSPHRASE_ARG),
+                )
+                .arg(
+                    Arg::with_name(PROMPT_PASSPHRASE_ARG)
+                        .help("Prompts for the bip39 passphrase of the seed")
+                        .short("P")
+                        .long(PROMPT_PASSPHRASE_ARG)
+                        .takes_value(false),
+                )
                 .arg(
                     Arg::with_name(ROOT_ARG)
                         .help("Derives xprv at bip32 root instead of account level")
@@ -167,6 +1152,13 @@ fn main() -> Result<(), String> {
                         .takes_value(false)
                         .conflicts_with_all(&[INDEX_ARG, NUMBER_ARG]),
                 )
+                .arg(
+                    Arg::with_name(PATH_ARG)
+                        .help("Derivation path to derive xprvs at instead of account level, like m/0h or m/84h/0h/{0..5}h. The type only sets the encoding")
+                        .long(PATH_ARG)
+                        .takes_value(true)
+                        .conflicts_with_all(&[ROOT_ARG, INDEX_ARG, NUMBER_ARG]),
+                )
                 .arg(
                     Arg::with_name(INDEX_ARG)
                         .help("Index to derive xprv at")
@@ -185,12 +1177,20 @@ fn main() -> Result<(), String> {
                 )
                 .arg(
                     Arg::with_name(TYPE_ARG)
-                        .help("Type of xprv to return")
+                        .help("Type of xprv to return. Taproot (tr) xprvs are serialized as xprv")
                         .short("t")
                         .long(TYPE_ARG)
                         .takes_value(true)
-                        .possible_values(&["xprv", "yprv", "zprv"])
+                        .possible_values(&["xprv", "yprv", "zprv", "tr"])
                         .default_value("zprv"),
+                )
+                .arg(
+                    Arg::with_name(NETWORK_ARG)
+                        .help("Network to derive xprvs for. Testnets use coin type 1 and testnet versions. Taken from the version of extended keys")
+                        .long(NETWORK_ARG)
+                        .takes_value(true)
+                        .possible_values(&["bitcoin", "testnet", "signet", "regtest"])
+                        .default_value("bitcoin"),
                 ),
         )
         .get_matches();
@@ -200,9 +1200,38 @@ fn main() -> Result<(), String> {
 /// Processes command line arguments.
 fn process_matches(matches: &ArgMatches) -> Result<(), String> {
     match matches.subcommand_name() {
+        Some(ADDRESSES_SUB) => {
+            process_addresses_matches(matches.subcommand_matches(ADDRESSES_SUB))?
+        }
         Some(CHILD_SUB) => process_child_matches(matches.subcommand_matches(CHILD_SUB))?,
+        Some(CHILD_HEX_SUB) => {
+            process_child_hex_matches(matches.subcommand_matches(CHILD_HEX_SUB))?
+        }
+        Some(CHILD_WIF_SUB) => {
+            process_child_wif_matches(matches.subcommand_matches(CHILD_WIF_SUB))?
+        }
+        Some(CHILD_XPRV_SUB) => {
+            process_child_xprv_matches(matches.subcommand_matches(CHILD_XPRV_SUB))?
+        }
+        Some(CONVERT_SUB) => process_convert_matches(matches.subcommand_matches(CONVERT_SUB))?,
+        Some(DESCRIPTOR_SUB) => {
+            process_descriptor_matches(matches.subcommand_matches(DESCRIPTOR_SUB))?
+        }
+        Some(DICE_SUB) => process_dice_matches(matches.subcommand_matches(DICE_SUB))?,
+        Some(ENTROPY_SUB) => process_entropy_matches(matches.subcommand_matches(ENTROPY_SUB))?,
         Some(EXTEND_SUB) => process_extend_matches(matches.subcommand_matches(EXTEND_SUB))?,
+        Some(FIND_CHILD_SUB) => {
+            process_find_child_matches(matches.subcommand_matches(FIND_CHILD_SUB))?
+        }
+        Some(GENERATE_SUB) => process_generate_matches(matches.subcommand_matches(GENERATE_SUB))?,
+        Some(INSPECT_SUB) => process_inspect_matches(matches.subcommand_matches(INSPECT_SUB))?,
+        Some(LAST_WORD_SUB) => {
+            process_last_word_matches(matches.subcommand_matches(LAST_WORD_SUB))?
+        }
+        Some(PASSWORD_SUB) => process_password_matches(matches.subcommand_matches(PASSWORD_SUB))?,
+        Some(RECOVER_SUB) => process_recover_matches(matches.subcommand_matches(RECOVER_SUB))?,
         Some(TRUNCATE_SUB) => process_truncate_matches(matches.subcommand_matches(TRUNCATE_SUB))?,
+        Some(VALIDATE_SUB) => process_validate_matches(matches.subcommand_matches(VALIDATE_SUB))?,
         Some(XOR_SUB) => process_xor_matches(matches.subcommand_matches(XOR_SUB))?,
         Some(XPUB_SUB) => process_xpub_matches(matches.subcommand_matches(XPUB_SUB))?,
         Some(XPRV_SUB) => process_xprv_matches(matches.subcommand_matches(XPRV_SUB))?,
@@ -213,61 +1242,451 @@ fn process_matches(matches: &ArgMatches) -> Result<(), String> {
     Ok(())
 }
 
-/// Returns the `index` flag's value.
-fn index_value(matches: Option<&ArgMatches>) -> Result<u32, String> {
+/// Returns the `index` flag's value.
+fn index_value(matches: Option<&ArgMatches>) -> Result<u32, String> {
+    matches
+        .unwrap()
+        .value_of(INDEX_ARG)
+        .ok_or_else(|| "index not set".to_string())?
+        .parse::<u32>()
+        .map_err(|_| "index must fit in a u32".to_string())
+}
+
+/// Returns the `index` flag's value as an index path like `3/7/0` or `None` if it is a single index.
+fn index_path_value(matches: Option<&ArgMatches>) -> Result<Option<Vec<u32>>, String> {
+    let index = matches
+        .unwrap()
+        .value_of(INDEX_ARG)
+        .ok_or_else(|| "index not set".to_string())?;
+    if !index.contains('/') {
+        return Ok(None);
+    }
+
+    index
+        .split('/')
+        .map(|i| {
+            i.parse::<u32>()
+                .map_err(|_| "index path needs to look like 3/7/0".to_string())
+        })
+        .collect::<Result<Vec<u32>, String>>()
+        .map(Some)
+}
+
+/// Returns the `number` flag's value.
+fn number_value(matches: Option<&ArgMatches>) -> Result<u32, String> {
+    matches
+        .unwrap()
+        .value_of(NUMBER_ARG)
+        .ok_or_else(|| "number not set".to_string())?
+        .parse::<u32>()
+        .map_err(|_| "number must fit in a u32".to_string())
+}
+
+/// Returns the index range `[index, index + number)` of the `index` and `number` flags.
+/// Fails if the range exceeds 2^31, because child indexes are hardened and address indexes are normal.
+fn range_value(matches: Option<&ArgMatches>) -> Result<(u32, u32), String> {
+    let index = index_value(matches)?;
+    let end = index
+        .checked_add(number_value(matches)?)
+        .filter(|end| *end <= 1 << 31)
+        .ok_or_else(|| "index + number can't be higher than 2^31".to_string())?;
+
+    Ok((index, end))
+}
+
+/// Splits an index range `[start, end)` into ranges of at most [CHUNK_SIZE] indexes.
+/// Subcommands which derive whole ranges at once print large ranges chunk by chunk.
+fn range_chunks((start, end): (u32, u32)) -> impl Iterator<Item = (u32, u32)> {
+    (start..end)
+        .step_by(CHUNK_SIZE as usize)
+        .map(move |chunk| (chunk, end.min(chunk.saturating_add(CHUNK_SIZE))))
+}
+
+/// Returns the `seed` flag's value.
+fn seed_value<'a>(matches: Option<&'a ArgMatches>) -> Result<&'a str, String> {
+    matches
+        .unwrap()
+        .value_of(SEED_ARG)
+        .ok_or_else(|| "seed not set".to_string())
+}
+
+/// Returns the `seed` flag's values as a list of seeds.
+fn seed_values<'a>(matches: Option<&'a ArgMatches>) -> Result<Vec<&'a str>, String> {
+    Ok(matches
+        .unwrap()
+        .values_of(SEED_ARG)
+        .ok_or_else(|| "seeds not set".to_string())?
+        .collect())
+}
+
+/// Returns the `words` flag's value.
+fn word_count_value(matches: Option<&ArgMatches>) -> Result<WordCount, String> {
+    let count = matches
+        .unwrap()
+        .value_of(WORDS_ARG)
+        .ok_or("word count not set")?;
+
+    WordCount::from_str(count).map_err(|e| e.to_string())
+}
+
+/// Returns the script type and version of the `type` flag's value.
+/// Taproot keys have no SLIP-132 version and are serialized with `taproot_version`.
+fn type_value(
+    matches: Option<&ArgMatches>,
+    taproot_version: Version,
+) -> Result<(ScriptType, Version), String> {
+    let version = matches
+        .unwrap()
+        .value_of(TYPE_ARG)
+        .ok_or_else(|| "type not set".to_string())?;
+    if version == "tr" {
+        return Ok((ScriptType::Tr, taproot_version));
+    }
+    let version = version_value(matches)?;
+    let script_type = ScriptType::from_version(&version).map_err(|e| e.to_string())?;
+
+    Ok((script_type, version))
+}
+
+/// Returns the SLIP-132 version of the `type` flag's value.
+fn version_value(matches: Option<&ArgMatches>) -> Result<Version, String> {
+    let version = matches
+        .unwrap()
+        .value_of(TYPE_ARG)
+        .ok_or_else(|| "type not set".to_string())?;
+    Version::from_str(version).map_err(|_| format!("Version prefix [{}] is not supported", version))
+}
+
+/// Returns the expanded derivation paths of the `path` flag's value.
+fn path_value(matches: Option<&ArgMatches>) -> Result<Option<Vec<DerivationPath>>, String> {
+    match matches.unwrap().value_of(PATH_ARG) {
+        Some(path) => seed_utils::expand_derivation_path(path)
+            .map(Some)
+            .map_err(|e| e.to_string()),
+        None => Ok(None),
+    }
+}
+
+/// Returns the `output-keys` flag's value.
+fn output_keys_value(matches: Option<&ArgMatches>) -> Result<u32, String> {
+    let output_keys = matches
+        .unwrap()
+        .value_of(OUTPUT_KEYS_ARG)
+        .ok_or_else(|| "output keys not set".to_string())?;
+    u32::from_str(output_keys).map_err(|e| e.to_string())
+}
+
+/// Returns the bip39 passphrase of the `passphrase` flag or prompts for it if the `prompt-passphrase` flag is present.
+/// Prints a warning if the passphrase begins or ends with whitespace.
+fn passphrase_value(matches: Option<&ArgMatches>) -> Result<Option<String>, String> {
+    let matches = matches.unwrap();
+    let passphrase = if matches.is_present(PROMPT_PASSPHRASE_ARG) {
+        let passphrase = rpassword::prompt_password_stderr("Passphrase: ")
+            .map_err(|e| format!("Failed to read passphrase: {}", e))?;
+        let confirmation = rpassword::prompt_password_stderr("Confirm passphrase: ")
+            .map_err(|e| format!("Failed to read passphrase: {}", e))?;
+        if passphrase != confirmation {
+            return Err("Passphrases do not match".to_string());
+        }
+        Some(passphrase)
+    } else {
+        matches.value_of(PASSPHRASE_ARG).map(|p| p.to_string())
+    };
+
+    if let Some(passphrase) = &passphrase {
+        if seed_utils::has_surrounding_whitespace(passphrase) {
+            eprintln!("Warning: passphrase begins or ends with whitespace, which is part of the passphrase");
+        }
+    }
+
+    Ok(passphrase)
+}
+
+/// Returns the `network` flag's value.
+fn network_value(matches: Option<&ArgMatches>) -> Result<Network, String> {
+    let network = matches
+        .unwrap()
+        .value_of(NETWORK_ARG)
+        .ok_or_else(|| "network not set".to_string())?;
+    Network::from_str(network).map_err(|_| format!("Network [{}] is not supported", network))
+}
+
+/// Returns the `language` flag's value or `None` if it is not set.
+fn language_value(matches: Option<&ArgMatches>) -> Result<Option<Language>, String> {
+    let language = match matches.unwrap().value_of(LANGUAGE_ARG) {
+        Some(language) => language,
+        None => return Ok(None),
+    };
+
+    match language {
+        "english" => Ok(Some(Language::English)),
+        "chinese-simplified" => Ok(Some(Language::SimplifiedChinese)),
+        "chinese-traditional" => Ok(Some(Language::TraditionalChinese)),
+        "czech" => Ok(Some(Language::Czech)),
+        "french" => Ok(Some(Language::French)),
+        "italian" => Ok(Some(Language::Italian)),
+        "japanese" => Ok(Some(Language::Japanese)),
+        "korean" => Ok(Some(Language::Korean)),
+        "portuguese" => Ok(Some(Language::Portuguese)),
+        "spanish" => Ok(Some(Language::Spanish)),
+        unknown => Err(format!("Language [{}] is not supported", unknown)),
+    }
+}
+
+/// Returns the `xpub` flag's value or `None` if it is not set.
+fn xpub_value(matches: Option<&ArgMatches>) -> Result<Option<Version>, String> {
+    match matches.unwrap().value_of(XPUB_ARG) {
+        Some(version) => Version::from_str(version)
+            .map(Some)
+            .map_err(|_| format!("Version prefix [{}] is not supported", version)),
+        None => Ok(None),
+    }
+}
+
+/// Returns the entropy source set by the `entropy`, `source` and `mix` flags or the OS random number generator if `entropy` is not set.
+/// Fails if the physical entropy has less than `bytes` bytes of entropy and warns if it looks biased.
+fn entropy_source_value(
+    matches: Option<&ArgMatches>,
+    bytes: usize,
+) -> Result<Box<dyn EntropySource>, String> {
+    let matches = matches.unwrap();
+    let values = match matches.value_of(ENTROPY_ARG) {
+        Some(values) => values,
+        None => return Ok(Box::new(rand::thread_rng())),
+    };
+    let source = matches
+        .value_of(SOURCE_ARG)
+        .ok_or_else(|| "source not set".to_string())?;
+    let source = PhysicalSource::from_str(source)
+        .map_err(|_| format!("Entropy source [{}] is not supported", source))?;
+    let entropy = PhysicalEntropy::parse(source, values).map_err(|e| e.to_string())?;
+
+    match entropy.values_needed(bytes) {
+        Some(0) => (),
+        Some(needed) => {
+            return Err(format!(
+                "Not enough entropy, {} more {} are needed",
+                needed,
+                source.value_name()
+            ))
+        }
+        None => {
+            return Err(format!(
+                "Not enough entropy, {} can't provide {} bits",
+                source.value_name(),
+                bytes * 8
+            ))
+        }
+    }
+    let mix = matches.is_present(MIX_ARG);
+    if entropy.is_biased() && !mix {
+        eprintln!(
+            "Warning: The {} look biased. Consider using --{}",
+            source.value_name(),
+            MIX_ARG
+        );
+    }
+
+    if mix {
+        Ok(Box::new(MixedEntropy::new(entropy, rand::thread_rng())))
+    } else {
+        Ok(Box::new(entropy))
+    }
+}
+
+/// Returns the `target` flag's value or `None` if it is not set.
+fn target_value(matches: Option<&ArgMatches>) -> Result<Option<RecoveryTarget>, String> {
+    match matches.unwrap().value_of(TARGET_ARG) {
+        Some(target) => RecoveryTarget::from_str(target)
+            .map(Some)
+            .map_err(|e| e.to_string()),
+        None => Ok(None),
+    }
+}
+
+/// Returns the `count` flag's value.
+fn count_value(matches: Option<&ArgMatches>) -> Result<u32, String> {
+    matches
+        .unwrap()
+        .value_of(COUNT_ARG)
+        .ok_or_else(|| "count not set".to_string())?
+        .parse::<u32>()
+        .map_err(|_| "count must fit in a u32".to_string())
+}
+
+/// Returns the `threads` flag's value.
+fn threads_value(matches: Option<&ArgMatches>) -> Result<usize, String> {
+    matches
+        .unwrap()
+        .value_of(THREADS_ARG)
+        .ok_or_else(|| "threads not set".to_string())?
+        .parse::<usize>()
+        .map_err(|_| "threads needs to be a positive number".to_string())
+}
+
+/// Returns the `swaps` flag.
+fn is_swaps(matches: Option<&ArgMatches>) -> bool {
+    matches.unwrap().is_present(SWAPS_ARG)
+}
+
+/// Returns the `script` flag's value.
+fn script_type_value(matches: Option<&ArgMatches>) -> Result<ScriptType, String> {
+    let script_type = matches
+        .unwrap()
+        .value_of(SCRIPT_ARG)
+        .ok_or_else(|| "script not set".to_string())?;
+    ScriptType::from_str(script_type).map_err(|e| e.to_string())
+}
+
+/// Returns the `random` flag.
+fn is_random(matches: Option<&ArgMatches>) -> bool {
+    matches.unwrap().is_present(RANDOM_ARG)
+}
+
+/// Returns the `account` flag's value.
+fn account_value(matches: Option<&ArgMatches>) -> Result<u32, String> {
+    matches
+        .unwrap()
+        .value_of(ACCOUNT_ARG)
+        .ok_or_else(|| "account not set".to_string())?
+        .parse::<u32>()
+        .map_err(|_| "account must fit in a u32".to_string())
+}
+
+/// Returns the chain of the `change` flag.
+fn chain_value(matches: Option<&ArgMatches>) -> Chain {
+    if matches.unwrap().is_present(CHANGE_ARG) {
+        Chain::Change
+    } else {
+        Chain::Receive
+    }
+}
+
+/// Returns the `seed` flag's value as an extended key with any SLIP-132 version or `None` if it is no extended key.
+/// Fails for extended keys if the `passphrase` or `prompt-passphrase` flag is present, because only seeds have a passphrase.
+fn key_value(matches: Option<&ArgMatches>) -> Result<Option<ExtendedKey>, String> {
+    let key = match ExtendedKey::from_str(seed_value(matches)?) {
+        Ok(key) => key,
+        Err(_) => return Ok(None),
+    };
+    let matches = matches.unwrap();
+    if matches.is_present(PASSPHRASE_ARG) || matches.is_present(PROMPT_PASSPHRASE_ARG) {
+        return Err("Passphrase can only be set for seeds, not for extended keys".to_string());
+    }
+
+    Ok(Some(key))
+}
+
+/// Returns the `seed` flag's value as a root key, which is either an extended key with any SLIP-132 version or
+/// the master key of a seed in the language of the `language` flag with the bip39 passphrase for the `network` flag or mainnet if it has none.
+/// The network of extended keys is taken from their version.
+fn root_key_value(matches: Option<&ArgMatches>) -> Result<ExtendedKey, String> {
+    let input = seed_value(matches)?;
+    if let Some(key) = key_value(matches)? {
+        return Ok(key);
+    }
+
+    // Keys don't depend on the language, but the seed has to be valid in it
+    seed_utils::parse_seed_in(input, language_value(matches)?).map_err(|e| e.to_string())?;
+    let passphrase = passphrase_value(matches)?;
+    // Subcommands without a network flag only derive network independent bip85 children
+    let network = if matches.unwrap().is_present(NETWORK_ARG) {
+        network_value(matches)?
+    } else {
+        Network::Bitcoin
+    };
+    let xprv = seed_utils::derive_root_xprv(input, passphrase.as_deref(), network)
+        .map_err(|e| e.to_string())?;
+
+    Ok(ExtendedKey::Private(xprv))
+}
+
+/// Returns the `seed` flag's value as an xprv like [root_key_value] or `Err` if it is an xpub.
+fn private_key_value(matches: Option<&ArgMatches>) -> Result<ExtendedPrivKey, String> {
+    match root_key_value(matches)? {
+        ExtendedKey::Private(xprv) => Ok(xprv),
+        ExtendedKey::Public(_) => Err("Bip85 children can't be derived from xpubs".to_string()),
+    }
+}
+
+/// Returns the `bytes` flag's value.
+fn bytes_value(matches: Option<&ArgMatches>) -> Result<u32, String> {
+    matches
+        .unwrap()
+        .value_of(BYTES_ARG)
+        .ok_or_else(|| "bytes not set".to_string())?
+        .parse::<u32>()
+        .map_err(|e| e.to_string())
+}
+
+/// Returns the `sides` flag's value.
+fn sides_value(matches: Option<&ArgMatches>) -> Result<u32, String> {
+    matches
+        .unwrap()
+        .value_of(SIDES_ARG)
+        .ok_or_else(|| "sides not set".to_string())?
+        .parse::<u32>()
+        .map_err(|e| e.to_string())
+}
+
+/// Returns the `rolls` flag's value.
+fn rolls_value(matches: Option<&ArgMatches>) -> Result<u32, String> {
     matches
         .unwrap()
-        .value_of(INDEX_ARG)
-        .ok_or_else(|| "index not set".to_string())?
+        .value_of(ROLLS_ARG)
+        .ok_or_else(|| "rolls not set".to_string())?
         .parse::<u32>()
-        .map_err(|_| "index can't be higher than 2^32".to_string())
+        .map_err(|e| e.to_string())
 }
 
-/// Returns the `number` flag's value.
-fn number_value(matches: Option<&ArgMatches>) -> Result<u8, String> {
+/// Returns the `length` flag's value.
+fn length_value(matches: Option<&ArgMatches>) -> Result<u32, String> {
     matches
         .unwrap()
-        .value_of(NUMBER_ARG)
-        .ok_or_else(|| "number not set".to_string())?
-        .parse::<u8>()
-        .map_err(|_| "number can't be higher than 255".to_string())
+        .value_of(LENGTH_ARG)
+        .ok_or_else(|| "length not set".to_string())?
+        .parse::<u32>()
+        .map_err(|e| e.to_string())
 }
 
-/// Returns the `seed` flag's value.
-fn seed_value<'a>(matches: Option<&'a ArgMatches>) -> Result<&'a str, String> {
+/// Returns the `encoding` flag's value.
+fn password_encoding_value(matches: Option<&ArgMatches>) -> Result<PasswordEncoding, String> {
     matches
         .unwrap()
-        .value_of(SEED_ARG)
-        .ok_or_else(|| "seed not set".to_string())
+        .value_of(ENCODING_ARG)
+        .ok_or_else(|| "encoding not set".to_string())?
+        .parse::<PasswordEncoding>()
+        .map_err(|e| e.to_string())
 }
 
-/// Returns the `seed` flag's values as a list of seeds.
-fn seed_values<'a>(matches: Option<&'a ArgMatches>) -> Result<Vec<&'a str>, String> {
-    Ok(matches
-        .unwrap()
-        .values_of(SEED_ARG)
-        .ok_or_else(|| "seeds not set".to_string())?
-        .into_iter()
-        .collect())
+/// Returns the root xprv of `key` to derive accounts from or `Err` if `key` is no root xprv.
+fn root_xprv(key: &ExtendedKey) -> Result<&ExtendedPrivKey, String> {
+    match key {
+        ExtendedKey::Private(xprv) if xprv.depth == 0 => Ok(xprv),
+        _ => Err(
+            "Accounts can only be derived from seeds or root xprvs, use --path for other keys"
+                .to_string(),
+        ),
+    }
 }
 
-/// Returns the `words` flag's value.
-fn word_count_value(matches: Option<&ArgMatches>) -> Result<WordCount, String> {
-    let count = matches
-        .unwrap()
-        .value_of(WORDS_ARG)
-        .ok_or("word count not set")?;
+/// Returns the network of an extended `key` or the `network` flag's value if it is set.
+/// Fails if the flag is set to mainnet for a testnet key or the other way around, because versions can't tell testnets apart.
+fn key_network_value(matches: Option<&ArgMatches>, key: &ExtendedKey) -> Result<Network, String> {
+    if matches.unwrap().occurrences_of(NETWORK_ARG) == 0 {
+        return Ok(key.network());
+    }
 
-    WordCount::from_str(count).map_err(|e| e.to_string())
-}
+    let network = network_value(matches)?;
+    if (network == Network::Bitcoin) != (key.network() == Network::Bitcoin) {
+        return Err(format!(
+            "Network [{}] doesn't match the network [{}] of the key",
+            network,
+            key.network()
+        ));
+    }
 
-/// Returns the `type` flag's value.
-fn type_value(matches: Option<&ArgMatches>) -> Result<Version, String> {
-    let version = matches
-        .unwrap()
-        .value_of(TYPE_ARG)
-        .ok_or_else(|| "type not set".to_string())?;
-    Version::from_str(version).map_err(|_| format!("Version prefix [{}] is not supported", version))
+    Ok(network)
 }
 
 /// Returns the `root` flag.
@@ -275,56 +1694,792 @@ fn is_root(matches: Option<&ArgMatches>) -> bool {
     matches.unwrap().is_present(ROOT_ARG)
 }
 
+/// Processes the `addresses` subcommand.
+fn process_addresses_matches(matches: Option<&ArgMatches>) -> Result<(), String> {
+    // Return early because every field is either required or has a default value
+    let input = seed_value(matches)?;
+    let range = range_value(matches)?;
+    let script_type = script_type_value(matches)?;
+    let chain = chain_value(matches);
+    let account = account_value(matches)?;
+
+    let key = key_value(matches)?;
+    let (network, passphrase) = match &key {
+        Some(key) => (key_network_value(matches, key)?, None),
+        None => {
+            // Keys don't depend on the language, but the seed has to be valid in it
+            seed_utils::parse_seed_in(input, language_value(matches)?)
+                .map_err(|e| e.to_string())?;
+            (network_value(matches)?, passphrase_value(matches)?)
+        }
+    };
+
+    // Root xprvs derive their account like seeds, other keys are the account
+    let account_xpub = match key {
+        Some(ExtendedKey::Private(xprv)) if xprv.depth == 0 => {
+            let end_account = account
+                .checked_add(1)
+                .ok_or_else(|| "account can't be higher than 2^31".to_string())?;
+            seed_utils::derive_xpubs_from_xprv(&xprv, (account, end_account), &script_type)
+                .map_err(|e| e.to_string())?
+                .pop()
+        }
+        Some(ExtendedKey::Public(xpub)) if xpub.depth == 0 => {
+            return Err("Accounts can't be derived from root xpubs".to_string())
+        }
+        Some(_) if matches.unwrap().occurrences_of(ACCOUNT_ARG) > 0 => {
+            return Err("Account can only be set for seeds and root xprvs".to_string())
+        }
+        // Paths of account keys are unknown, so address paths start at the key
+        Some(key) => Some((DerivationPath::master(), key.xpub())),
+        None => None,
+    };
+
+    for range in range_chunks(range) {
+        let derived = match &account_xpub {
+            Some((path, xpub)) => {
+                seed_utils::derive_addresses(xpub, path, &script_type, chain, range, network)
+            }
+            None => seed_utils::derive_addresses_from_seed(
+                input,
+                passphrase.as_deref(),
+                account,
+                &script_type,
+                chain,
+                range,
+                network,
+            ),
+        }
+        .map_err(|e| e.to_string())?;
+        for address in derived {
+            println!(
+                "Address at {}: {} with public key {}",
+                address.path, address.address, address.public_key
+            );
+        }
+    }
+
+    Ok(())
+}
+
 /// Processes the `child` subcommand.
 fn process_child_matches(matches: Option<&ArgMatches>) -> Result<(), String> {
     // Return early because every field is either required or has a default value
     let seed_str = seed_value(matches)?;
-    let index = index_value(matches)?;
-    let number = number_value(matches)?;
     let word_count = word_count_value(matches)?;
+    let language = language_value(matches)?;
+    if let Some(label) = matches.unwrap().value_of(LABEL_ARG) {
+        return print_labelled_child(matches, label, &word_count, language);
+    }
+    if let Some(indexes) = index_path_value(matches)? {
+        return print_child_lineage(matches, &indexes, &word_count, language);
+    }
+    let range = range_value(matches)?;
 
-    let derived =
-        seed_utils::derive_child_seeds(seed_str, (index, index + number as u32), &word_count)
-            .map_err(|e| e.to_string())?;
+    let derived: Box<dyn Iterator<Item = _>> = match key_value(matches)? {
+        Some(ExtendedKey::Private(xprv)) => Box::new(
+            seed_utils::iter_child_seeds_from_xprv(
+                &xprv,
+                range,
+                &word_count,
+                language.unwrap_or(Language::English),
+            )
+            .map_err(|e| e.to_string())?,
+        ),
+        Some(ExtendedKey::Public(_)) => {
+            return Err("Child seeds can't be derived from xpubs".to_string())
+        }
+        None => Box::new(
+            seed_utils::iter_child_seeds(
+                seed_str,
+                passphrase_value(matches)?.as_deref(),
+                range,
+                &word_count,
+                language,
+            )
+            .map_err(|e| e.to_string())?,
+        ),
+    };
 
-    for (i, mnemonic) in derived {
+    for derived in derived {
+        let (i, mnemonic) = derived.map_err(|e| e.to_string())?;
         println!("Index {}: {}", i, mnemonic);
     }
 
     Ok(())
 }
 
+/// Prints the child seed registered under `label` in the registry file of the `registry` flag.
+/// Unknown labels are registered with the `index`, `words` and `language` flags before and the registry file is written.
+/// These flags have to match the registered child seed if they are set for known labels.
+fn print_labelled_child(
+    matches: Option<&ArgMatches>,
+    label: &str,
+    word_count: &WordCount,
+    language: Option<Language>,
+) -> Result<(), String> {
+    if index_path_value(matches)?.is_some() || number_value(matches)? != 1 {
+        return Err("label can't be used with index paths or number".to_string());
+    }
+    // The language flag is the language of the child seed, so the seed's language is detected
+    let seed_str = seed_value(matches)?;
+    let xprv = match key_value(matches)? {
+        Some(ExtendedKey::Private(xprv)) => xprv,
+        Some(ExtendedKey::Public(_)) => {
+            return Err("Child seeds can't be derived from xpubs".to_string())
+        }
+        None => seed_utils::derive_root_xprv(
+            seed_str,
+            passphrase_value(matches)?.as_deref(),
+            Network::Bitcoin,
+        )
+        .map_err(|e| e.to_string())?,
+    };
+    let fingerprint = xprv.fingerprint(&Secp256k1::new());
+    let path = matches
+        .unwrap()
+        .value_of(REGISTRY_ARG)
+        .ok_or_else(|| "registry not set".to_string())?;
+
+    let mut registry = match std::fs::read_to_string(path) {
+        Ok(json) => ChildRegistry::from_json(&json).map_err(|e| e.to_string())?,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => ChildRegistry::new(fingerprint),
+        Err(e) => return Err(format!("Registry [{}] can't be read: {}", path, e)),
+    };
+    if registry.fingerprint() != fingerprint {
+        return Err(format!(
+            "Registry belongs to the seed with fingerprint {}, not {}",
+            registry.fingerprint(),
+            fingerprint
+        ));
+    }
+
+    let explicit_index = matches.unwrap().occurrences_of(INDEX_ARG) > 0;
+    let explicit_words = matches.unwrap().occurrences_of(WORDS_ARG) > 0;
+    let entry = match registry.entry(label) {
+        Some(entry) => {
+            if explicit_index && index_value(matches)? != entry.index {
+                return Err(format!(
+                    "Label [{}] is registered at index {}",
+                    label, entry.index
+                ));
+            }
+            if explicit_words && *word_count != entry.word_count {
+                return Err(format!(
+                    "Label [{}] is registered with {} words",
+                    label,
+                    entry.word_count.count()
+                ));
+            }
+            if language.is_some_and(|language| language != entry.language) {
+                return Err(format!(
+                    "Label [{}] is registered in {}",
+                    label, entry.language
+                ));
+            }
+            entry.clone()
+        }
+        None => {
+            let index = if explicit_index {
+                index_value(matches)?
+            } else {
+                registry.next_index()
+            };
+            // Children are in english by default like in derive_child_seeds
+            let language = language.unwrap_or(Language::English);
+            let entry = RegistryEntry {
+                label: label.to_string(),
+                index,
+                word_count: *word_count,
+                language,
+            };
+            registry
+                .register(entry.clone())
+                .map_err(|e| e.to_string())?;
+            std::fs::write(path, registry.to_json())
+                .map_err(|e| format!("Registry [{}] can't be written: {}", path, e))?;
+            entry
+        }
+    };
+
+    let end = entry.index.checked_add(1).ok_or("index is too high")?;
+    let derived = seed_utils::derive_child_seeds_from_xprv(
+        &xprv,
+        (entry.index, end),
+        &entry.word_count,
+        entry.language,
+    )
+    .map_err(|e| e.to_string())?;
+    for (i, mnemonic) in derived {
+        println!("Index {} ({}): {}", i, entry.label, mnemonic);
+    }
+
+    Ok(())
+}
+
+/// Prints the lineage of child seeds along `indexes` of the `seed` flag's value with their fingerprints.
+fn print_child_lineage(
+    matches: Option<&ArgMatches>,
+    indexes: &[u32],
+    word_count: &WordCount,
+    language: Option<Language>,
+) -> Result<(), String> {
+    if number_value(matches)? != 1 {
+        return Err("number can't be used with index paths".to_string());
+    }
+    let seed_str = seed_value(matches)?;
+
+    let (root_fingerprint, lineage) = match key_value(matches)? {
+        Some(ExtendedKey::Private(xprv)) => (
+            xprv.fingerprint(&Secp256k1::new()),
+            seed_utils::derive_child_seed_lineage_from_xprv(
+                &xprv,
+                indexes,
+                word_count,
+                language.unwrap_or(Language::English),
+            ),
+        ),
+        Some(ExtendedKey::Public(_)) => {
+            return Err("Child seeds can't be derived from xpubs".to_string())
+        }
+        None => {
+            let passphrase = passphrase_value(matches)?;
+            (
+                seed_utils::derive_fingerprint(seed_str, passphrase.as_deref())
+                    .map_err(|e| e.to_string())?,
+                seed_utils::derive_child_seed_lineage(
+                    seed_str,
+                    passphrase.as_deref(),
+                    indexes,
+                    word_count,
+                    language,
+                ),
+            )
+        }
+    };
+    let lineage = lineage.map_err(|e| e.to_string())?;
+
+    println!("Fingerprint of root: {}", root_fingerprint);
+    let mut path: Vec<String> = vec![];
+    for (i, mnemonic, fingerprint) in lineage {
+        path.push(i.to_string());
+        println!(
+            "Index {} (fingerprint {}): {}",
+            path.join("/"),
+            fingerprint,
+            mnemonic
+        );
+    }
+
+    Ok(())
+}
+
+/// Processes the `child-hex` subcommand.
+fn process_child_hex_matches(matches: Option<&ArgMatches>) -> Result<(), String> {
+    // Return early because every field is either required or has a default value
+    let xprv = private_key_value(matches)?;
+    let range = range_value(matches)?;
+    let bytes = bytes_value(matches)?;
+
+    for range in range_chunks(range) {
+        let derived =
+            seed_utils::derive_child_hex(&xprv, bytes, range).map_err(|e| e.to_string())?;
+        for (i, entropy) in derived {
+            println!("Index {}: {}", i, EntropyEncoding::Hex.encode(&entropy));
+        }
+    }
+
+    Ok(())
+}
+
+/// Processes the `child-wif` subcommand.
+fn process_child_wif_matches(matches: Option<&ArgMatches>) -> Result<(), String> {
+    // Return early because every field is either required or has a default value
+    let xprv = private_key_value(matches)?;
+    let range = range_value(matches)?;
+
+    for range in range_chunks(range) {
+        let derived = seed_utils::derive_child_wifs(&xprv, range).map_err(|e| e.to_string())?;
+        for (i, key) in derived {
+            println!("Index {}: {}", i, key);
+        }
+    }
+
+    Ok(())
+}
+
+/// Processes the `child-xprv` subcommand.
+fn process_child_xprv_matches(matches: Option<&ArgMatches>) -> Result<(), String> {
+    // Return early because every field is either required or has a default value
+    let xprv = private_key_value(matches)?;
+    let range = range_value(matches)?;
+
+    for range in range_chunks(range) {
+        let derived = seed_utils::derive_child_xprvs(&xprv, range).map_err(|e| e.to_string())?;
+        for (i, key) in derived {
+            println!("Index {}: {}", i, key);
+        }
+    }
+
+    Ok(())
+}
+
+/// Processes the `convert` subcommand.
+fn process_convert_matches(matches: Option<&ArgMatches>) -> Result<(), String> {
+    // Return early because every field is required
+    let key = matches
+        .unwrap()
+        .value_of(KEY_ARG)
+        .ok_or_else(|| "key not set".to_string())?;
+    let version = version_value(matches)?;
+
+    let from = seed_utils::key_version(key).map_err(|e| e.to_string())?;
+    let converted = seed_utils::convert_key_version(key, &version).map_err(|e| e.to_string())?;
+    println!(
+        "Converted {} to {}: {}",
+        seed_utils::version_prefix(&from),
+        seed_utils::version_prefix(&version),
+        converted
+    );
+
+    Ok(())
+}
+
+/// Processes the `descriptor` subcommand.
+fn process_descriptor_matches(matches: Option<&ArgMatches>) -> Result<(), String> {
+    // Return early because every field is either required or has a default value
+    let seed_str = seed_value(matches)?;
+    let passphrase = passphrase_value(matches)?;
+    let range = range_value(matches)?;
+    let script_type = script_type_value(matches)?;
+    let network = network_value(matches)?;
+
+    // Keys don't depend on the language, but the seed has to be valid in it
+    seed_utils::parse_seed_in(seed_str, language_value(matches)?).map_err(|e| e.to_string())?;
+
+    for range in range_chunks(range) {
+        let derived = seed_utils::derive_descriptors(
+            seed_str,
+            passphrase.as_deref(),
+            range,
+            &script_type,
+            network,
+        )
+        .map_err(|e| e.to_string())?;
+        for descriptors in derived {
+            println!(
+                "Receive descriptor at {}: {}",
+                descriptors.path, descriptors.receive
+            );
+            println!(
+                "Change descriptor at {}: {}",
+                descriptors.path, descriptors.change
+            );
+            println!(
+                "Multipath descriptor at {}: {}",
+                descriptors.path, descriptors.multipath
+            );
+        }
+    }
+
+    Ok(())
+}
+
+/// Processes the `dice` subcommand.
+fn process_dice_matches(matches: Option<&ArgMatches>) -> Result<(), String> {
+    // Return early because every field is either required or has a default value
+    let xprv = private_key_value(matches)?;
+    let range = range_value(matches)?;
+    let sides = sides_value(matches)?;
+    let rolls = rolls_value(matches)?;
+
+    for range in range_chunks(range) {
+        let derived =
+            seed_utils::derive_child_dice(&xprv, sides, rolls, range).map_err(|e| e.to_string())?;
+        for (i, rolls) in derived {
+            let rolls: Vec<String> = rolls.iter().map(|roll| roll.to_string()).collect();
+            println!("Index {}: {}", i, rolls.join(","));
+        }
+    }
+
+    Ok(())
+}
+
+/// Processes the `entropy` subcommand.
+fn process_entropy_matches(matches: Option<&ArgMatches>) -> Result<(), String> {
+    // Return early because input is required and from has a default
+    let input = matches
+        .unwrap()
+        .value_of(INPUT_ARG)
+        .ok_or_else(|| "input not set".to_string())?;
+    let from = matches
+        .unwrap()
+        .value_of(FROM_ARG)
+        .ok_or_else(|| "from not set".to_string())?;
+    let language = language_value(matches)?;
+
+    let seed = if from == "mnemonic" {
+        let seed = seed_utils::parse_seed_in(input, None).map_err(|e| e.to_string())?;
+        seed_utils::translate_seed(
+            seed.to_string(),
+            language.unwrap_or_else(|| seed.language()),
+        )
+    } else {
+        EntropyEncoding::from_str(from)
+            .and_then(|encoding| encoding.decode(input))
+            .and_then(|entropy| {
+                seed_utils::seed_from_entropy(&entropy, language.unwrap_or(Language::English))
+            })
+    }
+    .map_err(|e| e.to_string())?;
+    let entropy = seed.to_entropy();
+
+    println!("Seed: {}", seed);
+    println!("Hex: {}", EntropyEncoding::Hex.encode(&entropy));
+    println!("Binary: {}", EntropyEncoding::Binary.encode(&entropy));
+    println!("Base64: {}", EntropyEncoding::Base64.encode(&entropy));
+
+    Ok(())
+}
+
 /// Processes the `extend` subcommand.
 fn process_extend_matches(matches: Option<&ArgMatches>) -> Result<(), String> {
     // Return early because every field is either required or has a default value
     let seed_str = seed_value(matches)?;
     let word_count = word_count_value(matches)?;
+    let language = language_value(matches)?;
+    let seed_bytes = seed_utils::parse_seed_in(seed_str, None)
+        .map_err(|e| e.to_string())?
+        .to_entropy()
+        .len();
+    let mut entropy_source = entropy_source_value(
+        matches,
+        word_count.entropy_bytes().saturating_sub(seed_bytes),
+    )?;
 
     let extended_seed =
-        seed_utils::extend_seed(seed_str, &word_count).map_err(|e| e.to_string())?;
+        seed_utils::extend_seed(seed_str, &word_count, language, &mut *entropy_source)
+            .map_err(|e| e.to_string())?;
     println!("Extended seed: {}", extended_seed);
 
     Ok(())
 }
 
+/// Processes the `find-child` subcommand.
+fn process_find_child_matches(matches: Option<&ArgMatches>) -> Result<(), String> {
+    // Return early because every field is either required or has a default value
+    let seed_str = seed_value(matches)?;
+    let child = matches
+        .unwrap()
+        .value_of(CHILD_ARG)
+        .ok_or_else(|| "child not set".to_string())?;
+    let target = ChildTarget::from_str(child).map_err(|e| e.to_string())?;
+    let index = index_value(matches)?;
+    let end = index
+        .checked_add(count_value(matches)?)
+        .ok_or("index and count can't exceed 2^32")?;
+    let threads = threads_value(matches)?;
+
+    let xprv = match key_value(matches)? {
+        Some(ExtendedKey::Private(xprv)) => xprv,
+        Some(ExtendedKey::Public(_)) => {
+            return Err("Child seeds can't be derived from xpubs".to_string())
+        }
+        None => seed_utils::derive_root_xprv(
+            seed_str,
+            passphrase_value(matches)?.as_deref(),
+            Network::Bitcoin,
+        )
+        .map_err(|e| e.to_string())?,
+    };
+    // Children are in english by default like in derive_child_seeds
+    let language = language_value(matches)?.unwrap_or(Language::English);
+
+    // Only print progress when it changed by at least a percent
+    let last_percent = AtomicU64::new(0);
+    let found = seed_utils::find_child_seed(
+        &xprv,
+        &target,
+        language,
+        (index, end),
+        threads,
+        |checked, total| {
+            let percent = checked * 100 / total;
+            if last_percent.fetch_max(percent, Ordering::Relaxed) < percent {
+                eprint!("\rChecked {}/{} indexes ({}%)", checked, total, percent);
+            }
+        },
+    )
+    .map_err(|e| e.to_string())?;
+    eprintln!();
+
+    if found.is_empty() {
+        println!("No child found");
+    }
+    for (i, mnemonic) in found {
+        println!("Index {}: {}", i, mnemonic);
+    }
+
+    Ok(())
+}
+
+/// Processes the `generate` subcommand.
+fn process_generate_matches(matches: Option<&ArgMatches>) -> Result<(), String> {
+    // Return early because every field is either optional or has a default value
+    let word_count = word_count_value(matches)?;
+    let language = language_value(matches)?.unwrap_or(Language::English);
+    let passphrase = passphrase_value(matches)?;
+    let network = network_value(matches)?;
+    let mut entropy_source = entropy_source_value(matches, word_count.entropy_bytes())?;
+
+    let seed = seed_utils::generate_seed(&word_count, language, &mut *entropy_source)
+        .map_err(|e| e.to_string())?
+        .to_string();
+    let fingerprint =
+        seed_utils::derive_fingerprint(&seed, passphrase.as_deref()).map_err(|e| e.to_string())?;
+    println!("Generated seed: {}", seed);
+    println!("Fingerprint: {}", fingerprint);
+
+    // Print first account xpub if flag is present
+    if let Some(version) = xpub_value(matches)? {
+        let version = seed_utils::version_for_network(&version, network);
+        let derived = seed_utils::derive_xpubs_from_seed(
+            &seed,
+            passphrase.as_deref(),
+            (0, 1),
+            &version,
+            network,
+        )
+        .map_err(|e| e.to_string())?;
+        for (i, xpub) in derived {
+            println!(
+                "Derived xpub at {}: {}{}",
+                i,
+                seed_utils::key_origin(&fingerprint, &i),
+                xpub.versioned_string(&version).map_err(|e| e.to_string())?
+            );
+        }
+    }
+
+    Ok(())
+}
+
+/// Processes the `inspect` subcommand.
+fn process_inspect_matches(matches: Option<&ArgMatches>) -> Result<(), String> {
+    let input = matches
+        .unwrap()
+        .value_of(INPUT_ARG)
+        .ok_or_else(|| "input not set".to_string())?;
+
+    if let Ok(version) = seed_utils::key_version(input) {
+        let key = ExtendedKey::from_str(input).map_err(|e| e.to_string())?;
+        let child_number = match key.child_number() {
+            ChildNumber::Normal { index } => format!("{} (normal)", index),
+            ChildNumber::Hardened { index } => format!("{}h (hardened)", index),
+        };
+        let version_bytes: String = version
+            .bytes()
+            .iter()
+            .map(|b| format!("{:02x}", b))
+            .collect();
+        println!(
+            "Version: {} ({})",
+            seed_utils::version_prefix(&version),
+            version_bytes
+        );
+        println!("Meaning: {}", seed_utils::version_description(&version));
+        println!("Network: {}", key.network());
+        println!("Depth: {}", key.depth());
+        println!("Parent fingerprint: {}", key.parent_fingerprint());
+        println!("Child number: {}", child_number);
+        println!("Chain code: {}", key.chain_code());
+        println!("Public key: {}", key.xpub().public_key);
+        println!("Fingerprint: {}", key.fingerprint());
+
+        return Ok(());
+    }
+
+    let report = seed_utils::validate_seed(input, language_value(matches)?, None)
+        .map_err(|e| e.to_string())?;
+    if !report.is_valid() {
+        return Err(
+            "Input is neither an extended key nor a valid seed, use validate for details"
+                .to_string(),
+        );
+    }
+    println!("Type: bip39 seed");
+    println!("Language: {}", report.language);
+    println!("Word count: {}", report.word_count);
+    if let Some(entropy_bits) = report.entropy_bits {
+        println!("Entropy: {} bits", entropy_bits);
+    }
+    if let Some(fingerprint) = report.fingerprint {
+        println!("Fingerprint without passphrase: {}", fingerprint);
+    }
+
+    Ok(())
+}
+
+/// Processes the `last-word` subcommand.
+fn process_last_word_matches(matches: Option<&ArgMatches>) -> Result<(), String> {
+    let seed_str = seed_value(matches)?;
+    let language = language_value(matches)?;
+
+    let last_words = seed_utils::last_words(seed_str, language).map_err(|e| e.to_string())?;
+    if is_random(matches) {
+        let last_word = last_words
+            .choose(&mut rand::thread_rng())
+            .ok_or_else(|| "No valid last word found".to_string())?;
+        println!(
+            "Last word: {} ({})",
+            last_word.word(),
+            bits_string(last_word)
+        );
+        println!("Seed: {}", last_word.seed);
+        return Ok(());
+    }
+
+    if let Some(last_word) = last_words.first() {
+        println!(
+            "Valid last words ({} entropy bits + {} checksum bits):",
+            last_word.entropy_bits(),
+            last_word.checksum_bits
+        );
+    }
+    for last_word in last_words.iter() {
+        println!("{} {}", bits_string(last_word), last_word.word());
+    }
+
+    Ok(())
+}
+
+/// Returns the bits of `last_word`'s index with its entropy and checksum bits separated by a space.
+fn bits_string(last_word: &LastWord) -> String {
+    let bits = format!("{:011b}", last_word.index);
+    let (entropy, checksum) = bits.split_at(last_word.entropy_bits() as usize);
+    format!("{} {}", entropy, checksum)
+}
+
+/// Processes the `password` subcommand.
+fn process_password_matches(matches: Option<&ArgMatches>) -> Result<(), String> {
+    // Return early because every field is either required or has a default value
+    let xprv = private_key_value(matches)?;
+    let range = range_value(matches)?;
+    let length = length_value(matches)?;
+    let encoding = password_encoding_value(matches)?;
+
+    for range in range_chunks(range) {
+        let derived = seed_utils::derive_child_passwords(&xprv, length, &encoding, range)
+            .map_err(|e| e.to_string())?;
+        for (i, password) in derived {
+            println!("Index {}: {}", i, password);
+        }
+    }
+
+    Ok(())
+}
+
+/// Processes the `recover` subcommand.
+fn process_recover_matches(matches: Option<&ArgMatches>) -> Result<(), String> {
+    let seed_str = seed_value(matches)?;
+    let language = language_value(matches)?;
+    let passphrase = passphrase_value(matches)?;
+    let target = target_value(matches)?;
+    let threads = threads_value(matches)?;
+
+    // Only print progress when it changed by at least a percent
+    let last_percent = AtomicU64::new(0);
+    let recovered = seed_utils::recover_seed(
+        seed_str,
+        language,
+        passphrase.as_deref(),
+        target.as_ref(),
+        is_swaps(matches),
+        threads,
+        |checked, total| {
+            let percent = checked * 100 / total;
+            if last_percent.fetch_max(percent, Ordering::Relaxed) < percent {
+                eprint!("\rChecked {}/{} candidates ({}%)", checked, total, percent);
+            }
+        },
+    )
+    .map_err(|e| e.to_string())?;
+    eprintln!();
+
+    if recovered.is_empty() {
+        println!("No seed found");
+    }
+    for seed in recovered {
+        println!("Recovered seed: {}", seed);
+    }
+
+    Ok(())
+}
+
 /// Processes the `truncate` subcommand.
 fn process_truncate_matches(matches: Option<&ArgMatches>) -> Result<(), String> {
     // Return early because seed is required and word count has a default
     let seed_str = seed_value(matches)?;
     let word_count = word_count_value(matches)?;
+    let language = language_value(matches)?;
 
     let truncated_seed =
-        seed_utils::truncate_seed(&seed_str, &word_count).map_err(|e| e.to_string())?;
+        seed_utils::truncate_seed(seed_str, &word_count, language).map_err(|e| e.to_string())?;
     println!("Truncated seed: {}", truncated_seed);
 
     Ok(())
 }
 
+/// Processes the `validate` subcommand.
+fn process_validate_matches(matches: Option<&ArgMatches>) -> Result<(), String> {
+    let seed_str = seed_value(matches)?;
+    let language = language_value(matches)?;
+    let passphrase = passphrase_value(matches)?;
+
+    let report = seed_utils::validate_seed(seed_str, language, passphrase.as_deref())
+        .map_err(|e| e.to_string())?;
+    println!("Language: {}", report.language);
+    if report.word_count_valid {
+        println!("Word count: {}", report.word_count);
+    } else {
+        println!(
+            "Word count: {} (needs to be either 12, 15, 18, 21 or 24)",
+            report.word_count
+        );
+    }
+    for unknown in report.unknown_words.iter() {
+        println!(
+            "Unknown word {}: {} (did you mean {}?)",
+            unknown.position,
+            unknown.word,
+            unknown.suggestions.join(", ")
+        );
+    }
+    match report.checksum_valid {
+        Some(true) => println!("Checksum: valid"),
+        Some(false) => println!("Checksum: invalid"),
+        None => println!("Checksum: not checked"),
+    }
+    if let Some(entropy_bits) = report.entropy_bits {
+        println!("Entropy: {} bits", entropy_bits);
+    }
+    if let Some(fingerprint) = report.fingerprint {
+        println!("Fingerprint: {}", fingerprint);
+    }
+
+    if report.is_valid() {
+        Ok(())
+    } else {
+        Err("Seed is invalid".to_string())
+    }
+}
+
 /// Processes the `xor` subcommand.
 fn process_xor_matches(matches: Option<&ArgMatches>) -> Result<(), String> {
     let seeds = seed_values(matches)?;
+    let language = language_value(matches)?;
 
-    if let Some(xor) = seed_utils::xor_seeds(&seeds).map_err(|e| e.to_string())? {
+    if let Some(xor) = seed_utils::xor_seeds(&seeds, language).map_err(|e| e.to_string())? {
         println!("XORed seed: {}", xor);
     } else {
         println!("No seeds to XOR");
@@ -336,31 +2491,91 @@ fn process_xor_matches(matches: Option<&ArgMatches>) -> Result<(), String> {
 /// Processes the `xpub` subcommand.
 fn process_xpub_matches(matches: Option<&ArgMatches>) -> Result<(), String> {
     // Return early because every field is either required or has a default value
-    let seed_str = seed_value(matches)?;
-    let version = type_value(matches)?;
+    let key = root_key_value(matches)?;
+    let network = key.network();
+    let (script_type, version) = type_value(matches, Version::Xpub)?;
+    let version = seed_utils::version_for_network(&version, network);
+    let fingerprint = key.fingerprint();
 
     // Print root key if flag is present
     if is_root(matches) {
-        let master = seed_utils::derive_root_xpub(seed_str)
-            .map_err(|e| e.to_string())?
-            .versioned_string(&version)?;
-        println!("Root xpub: {}", master);
+        if key.depth() != 0 {
+            return Err("Key is no root key".to_string());
+        }
+        let master = key
+            .xpub()
+            .versioned_string(&version)
+            .map_err(|e| e.to_string())?;
+        println!("Root xpub: [{}]{}", fingerprint, master);
+
+        return Ok(());
+    }
+
+    // Derive keys at custom derivation paths if flag is present
+    if let Some(paths) = path_value(matches)? {
+        let derived = seed_utils::derive_keys_at_paths(&key, &paths).map_err(|e| e.to_string())?;
+        for (i, derived_key) in derived {
+            println!(
+                "Derived xpub at {}: {}{}",
+                i,
+                seed_utils::key_origin(&fingerprint, &i),
+                derived_key
+                    .xpub()
+                    .versioned_string(&version)
+                    .map_err(|e| e.to_string())?
+            );
+        }
 
         return Ok(());
     }
 
     // Derive extended public keys
-    let index = index_value(matches)?;
-    let number = number_value(matches)?;
+    let root = root_xprv(&key)?;
+    let range = range_value(matches)?;
+    if script_type == ScriptType::Tr {
+        return print_taproot_accounts(root, range, output_keys_value(matches)?);
+    }
     let derived =
-        seed_utils::derive_xpubs_from_seed(seed_str, (index, index + number as u32), &version)
-            .map_err(|e| e.to_string())?;
-    for (i, xpub) in derived {
+        seed_utils::iter_xpubs_from_xprv(root, range, &script_type).map_err(|e| e.to_string())?;
+    for derived in derived {
+        let (i, xpub) = derived.map_err(|e| e.to_string())?;
         println!(
-            "Derived xpub at {}: {}",
+            "Derived xpub at {}: {}{}",
             i,
-            xpub.versioned_string(&version)?
+            seed_utils::key_origin(&fingerprint, &i),
+            xpub.versioned_string(&version).map_err(|e| e.to_string())?
+        );
+    }
+
+    Ok(())
+}
+
+/// Prints the xpubs, `tr()` descriptors and the first `output_keys` tweaked receive output keys of taproot accounts.
+fn print_taproot_accounts(
+    root: &ExtendedPrivKey,
+    (start, end): (u32, u32),
+    output_keys: u32,
+) -> Result<(), String> {
+    let secp = Secp256k1::new();
+    let fingerprint = root.fingerprint(&secp);
+    let derived = seed_utils::iter_xpubs_from_xprv(root, (start, end), &ScriptType::Tr)
+        .map_err(|e| e.to_string())?;
+    for derived in derived {
+        let (path, xpub) = derived.map_err(|e| e.to_string())?;
+        let account = AccountDescriptors::new(&ScriptType::Tr, &fingerprint, path, xpub);
+        println!(
+            "Derived xpub at {}: {}{}",
+            account.path,
+            seed_utils::key_origin(&fingerprint, &account.path),
+            account.xpub
         );
+        println!("Descriptor at {}: {}", account.path, account.multipath);
+        let keys =
+            seed_utils::derive_taproot_output_keys(&account.xpub, Chain::Receive, (0, output_keys))
+                .map_err(|e| e.to_string())?;
+        for (i, key) in keys {
+            println!("Output key at {}/0/{}: {}", account.path, i, key);
+        }
     }
 
     Ok(())
@@ -369,53 +2584,58 @@ fn process_xpub_matches(matches: Option<&ArgMatches>) -> Result<(), String> {
 /// Processes the `xprv` subcommand.
 fn process_xprv_matches(matches: Option<&ArgMatches>) -> Result<(), String> {
     // Return early because every field is either required or has a default value
-    let seed_str = seed_value(matches)?;
-    let version = type_value(matches)?;
+    let key = root_key_value(matches)?;
+    let network = key.network();
+    let (script_type, version) = type_value(matches, Version::Xprv)?;
+    let version = seed_utils::version_for_network(&version, network);
+    let fingerprint = key.fingerprint();
+    let xprv = match key {
+        ExtendedKey::Private(xprv) => xprv,
+        ExtendedKey::Public(_) => return Err("Xprvs can't be derived from xpubs".to_string()),
+    };
 
     // Print root key if flag is present
     if is_root(matches) {
-        let master = seed_utils::derive_root_xprv(seed_str)
-            .map_err(|e| e.to_string())?
-            .versioned_string(&version)?;
-        println!("Root xprv: {}", master);
+        if xprv.depth != 0 {
+            return Err("Key is no root key".to_string());
+        }
+        let master = xprv.versioned_string(&version).map_err(|e| e.to_string())?;
+        println!("Root xprv: [{}]{}", fingerprint, master);
+
+        return Ok(());
+    }
+
+    // Derive keys at custom derivation paths if flag is present
+    if let Some(paths) = path_value(matches)? {
+        let derived = seed_utils::derive_keys_at_paths(&key, &paths).map_err(|e| e.to_string())?;
+        for (i, derived_key) in derived {
+            println!(
+                "Derived xprv at {}: {}{}",
+                i,
+                seed_utils::key_origin(&fingerprint, &i),
+                derived_key
+                    .versioned_string(&version)
+                    .map_err(|e| e.to_string())?
+            );
+        }
 
         return Ok(());
     }
 
     // Derive extended private keys
-    let index = index_value(matches)?;
-    let number = number_value(matches)?;
+    let root = root_xprv(&key)?;
+    let range = range_value(matches)?;
     let derived =
-        seed_utils::derive_xprvs_from_seed(seed_str, (index, index + number as u32), &version)
-            .map_err(|e| e.to_string())?;
-    for (i, xpub) in derived {
+        seed_utils::iter_xprvs_from_xprv(root, range, &script_type).map_err(|e| e.to_string())?;
+    for derived in derived {
+        let (i, xprv) = derived.map_err(|e| e.to_string())?;
         println!(
-            "Derived xprv at {}: {}",
+            "Derived xprv at {}: {}{}",
             i,
-            xpub.versioned_string(&version)?
+            seed_utils::key_origin(&fingerprint, &i),
+            xprv.versioned_string(&version).map_err(|e| e.to_string())?
         );
     }
 
     Ok(())
 }
-
-/// Trait for returning a versioned string of a Bitcoin address.
-trait VersionedString {
-    /// Returns a versioned string or `Err` if conversion fails.
-    /// Failure may occur when `self`'s string value is not a valid base58 check encoded Bitcoin address.
-    fn versioned_string(&self, version: &Version) -> Result<String, String>;
-}
-
-impl VersionedString for ExtendedPubKey {
-    fn versioned_string(&self, version: &Version) -> Result<String, String> {
-        xyzpub::convert_version(self.to_string(), version)
-            .map_err(|_| "Failed to convert extended public key to requested version".to_string())
-    }
-}
-
-impl VersionedString for ExtendedPrivKey {
-    fn versioned_string(&self, version: &Version) -> Result<String, String> {
-        xyzpub::convert_version(self.to_string(), version)
-            .map_err(|_| "Failed to convert extended private key to requested version".to_string())
-    }
-}
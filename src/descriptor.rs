@@ -0,0 +1,205 @@
+//! Output descriptors of derived accounts.
+use bitcoin::util::bip32::{DerivationPath, ExtendedPubKey, Fingerprint};
+use bitcoin::Network;
+
+use crate::{derive_fingerprint, derive_script_xpubs_from_seed, key_origin, Error, ScriptType};
+
+/// Characters allowed in descriptors, ordered by their checksum value.
+const INPUT_CHARSET: &str = "0123456789()[],'/*abcdefgh@:$%{}IJKLMNOPQRSTUVWXYZ&+-.;<=>?!^_|~ijklmnopqrstuvwxyzABCDEFGH`#\"\\ ";
+/// Characters of the checksum.
+const CHECKSUM_CHARSET: &[u8] = b"qpzry9x8gf2tvdw0s3jn54khce6mua7l";
+
+/// Output descriptors with checksums of an account.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AccountDescriptors {
+    /// Derivation path of the account.
+    pub path: DerivationPath,
+    /// Extended public key of the account.
+    pub xpub: ExtendedPubKey,
+    /// Descriptor of the receive addresses at `/0/*`.
+    pub receive: String,
+    /// Descriptor of the change addresses at `/1/*`.
+    pub change: String,
+    /// Multipath descriptor of the receive and change addresses at `/<0;1>/*`.
+    pub multipath: String,
+}
+
+impl AccountDescriptors {
+    /// Creates the descriptors of `script_type` for an account `xpub` derived at `path` from a root key with `fingerprint`.
+    pub fn new(
+        script_type: &ScriptType,
+        fingerprint: &Fingerprint,
+        path: DerivationPath,
+        xpub: ExtendedPubKey,
+    ) -> Self {
+        let key = format!("{}{}", key_origin(fingerprint, &path), xpub);
+        AccountDescriptors {
+            receive: with_checksum(&wrap_key(script_type, &format!("{}/0/*", key))),
+            change: with_checksum(&wrap_key(script_type, &format!("{}/1/*", key))),
+            multipath: with_checksum(&wrap_key(script_type, &format!("{}/<0;1>/*", key))),
+            path,
+            xpub,
+        }
+    }
+}
+
+/// Derives output descriptors of `script_type` for the accounts of a `seed` with an index range of `[start, end)`.
+/// The optional bip39 `passphrase` is applied to `seed` before deriving.
+/// Accounts are derived like in [derive_script_xpubs_from_seed].
+pub fn derive_descriptors<S>(
+    seed: S,
+    passphrase: Option<&str>,
+    (start, end): (u32, u32),
+    script_type: &ScriptType,
+    network: Network,
+) -> Result<Vec<AccountDescriptors>, Error>
+where
+    S: AsRef<str>,
+{
+    let fingerprint = derive_fingerprint(&seed, passphrase)?;
+    let xpubs =
+        derive_script_xpubs_from_seed(seed, passphrase, (start, end), script_type, network)?;
+
+    let result = xpubs
+        .into_iter()
+        .map(|(path, xpub)| AccountDescriptors::new(script_type, &fingerprint, path, xpub))
+        .collect();
+
+    Ok(result)
+}
+
+/// Returns the bip380 checksum of `descriptor` or `None` if it contains characters which are not allowed in descriptors.
+pub fn descriptor_checksum(descriptor: &str) -> Option<String> {
+    let mut checksum = 1u64;
+    let mut classes = 0u64;
+    let mut class_count = 0;
+
+    for c in descriptor.chars() {
+        let position = INPUT_CHARSET.find(c)? as u64;
+        checksum = polymod(checksum, position & 31);
+        classes = classes * 3 + (position >> 5);
+        class_count += 1;
+        if class_count == 3 {
+            checksum = polymod(checksum, classes);
+            classes = 0;
+            class_count = 0;
+        }
+    }
+    if class_count > 0 {
+        checksum = polymod(checksum, classes);
+    }
+    for _ in 0..8 {
+        checksum = polymod(checksum, 0);
+    }
+    checksum ^= 1;
+
+    Some(
+        (0..8)
+            .map(|i| CHECKSUM_CHARSET[((checksum >> (5 * (7 - i))) & 31) as usize] as char)
+            .collect(),
+    )
+}
+
+/// Returns a descriptor of `script_type` for `key` without checksum.
+fn wrap_key(script_type: &ScriptType, key: &str) -> String {
+    match script_type {
+        ScriptType::Pkh => format!("pkh({})", key),
+        ScriptType::ShWpkh => format!("sh(wpkh({}))", key),
+        ScriptType::Wpkh => format!("wpkh({})", key),
+        ScriptType::Tr => format!("tr({})", key),
+    }
+}
+
+/// Returns `descriptor` with its checksum appended after a `#`.
+fn with_checksum(descriptor: &str) -> String {
+    // Generated descriptors only contain allowed characters
+    let checksum = descriptor_checksum(descriptor).unwrap_or_default();
+    format!("{}#{}", descriptor, checksum)
+}
+
+/// Feeds `value` into the checksum `c`.
+fn polymod(c: u64, value: u64) -> u64 {
+    let c0 = c >> 35;
+    let mut c = ((c & 0x7ffffffff) << 5) ^ value;
+    if c0 & 1 != 0 {
+        c ^= 0xf5dee51989;
+    }
+    if c0 & 2 != 0 {
+        c ^= 0xa9fdca3312;
+    }
+    if c0 & 4 != 0 {
+        c ^= 0x1bab10e32d;
+    }
+    if c0 & 8 != 0 {
+        c ^= 0x3706b1677a;
+    }
+    if c0 & 16 != 0 {
+        c ^= 0x644d626ffd;
+    }
+
+    c
+}
+
+#[cfg(test)]
+mod tests {
+    use bitcoin::Network;
+
+    use crate::descriptor::{derive_descriptors, descriptor_checksum};
+    use crate::ScriptType;
+
+    const SEED: &str = "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about";
+
+    #[test]
+    fn descriptor_checksum_returns_checksum() {
+        assert_eq!(
+            descriptor_checksum("raw(deadbeef)"),
+            Some("89f8spxm".to_string())
+        );
+        assert_eq!(descriptor_checksum("raw(deadbeef)\u{e9}"), None);
+    }
+
+    #[test]
+    fn derive_descriptors_returns_descriptors() {
+        let result =
+            derive_descriptors(SEED, None, (0, 2), &ScriptType::Wpkh, Network::Bitcoin).unwrap();
+        assert_eq!(result.len(), 2);
+        assert_eq!(result[0].path.to_string(), "m/84'/0'/0'");
+        assert_eq!(result[1].path.to_string(), "m/84'/0'/1'");
+
+        let key = "[73c5da0a/84h/0h/0h]xpub6CatWdiZiodmUeTDp8LT5or8nmbKNcuyvz7WyksVFkKB4RHwCD3XyuvPEbvqAQY3rAPshWcMLoP2fMFMKHPJ4ZeZXYVUhLv1VMrjPC7PW6V";
+        let receive = format!("wpkh({}/0/*)", key);
+        let change = format!("wpkh({}/1/*)", key);
+        let multipath = format!("wpkh({}/<0;1>/*)", key);
+        for (descriptor, expected) in [
+            (&result[0].receive, receive),
+            (&result[0].change, change),
+            (&result[0].multipath, multipath),
+        ]
+        .iter()
+        {
+            let checksum = descriptor_checksum(expected).unwrap();
+            assert_eq!(*descriptor, &format!("{}#{}", expected, checksum));
+        }
+    }
+
+    #[test]
+    fn derive_descriptors_wraps_script_types() {
+        let script_types = [
+            (ScriptType::Pkh, "pkh([73c5da0a/44h/1h/0h]tpub"),
+            (ScriptType::ShWpkh, "sh(wpkh([73c5da0a/49h/1h/0h]tpub"),
+            (ScriptType::Wpkh, "wpkh([73c5da0a/84h/1h/0h]tpub"),
+            (ScriptType::Tr, "tr([73c5da0a/86h/1h/0h]tpub"),
+        ];
+
+        for (script_type, prefix) in script_types.iter() {
+            let result =
+                derive_descriptors(SEED, None, (0, 1), script_type, Network::Testnet).unwrap();
+            assert!(result[0].receive.starts_with(prefix));
+        }
+
+        // Taproot account xpub of bip86
+        let result =
+            derive_descriptors(SEED, None, (0, 1), &ScriptType::Tr, Network::Bitcoin).unwrap();
+        assert_eq!(result[0].xpub.to_string(), "xpub6BgBgsespWvERF3LHQu6CnqdvfEvtMcQjYrcRzx53QJjSxarj2afYWcLteoGVky7D3UKDP9QyrLprQ3VCECoY49yfdDEHGCtMMj92pReUsQ");
+    }
+}
@@ -0,0 +1,531 @@
+//! Entropy sources for new and extended seeds.
+//!
+//! Besides random number generators, entropy can come from physical sources like dice rolls,
+//! coin flips or the order of a shuffled deck of cards.
+use std::fmt;
+use std::str::FromStr;
+
+use bitcoin::hashes::hex::{FromHex, ToHex};
+use bitcoin::hashes::{sha256, Hash};
+use rand::{CryptoRng, RngCore};
+
+use crate::Error;
+
+const DECK_SIZE: usize = 52;
+const CARD_RANKS: [&str; 13] = [
+    "A", "2", "3", "4", "5", "6", "7", "8", "9", "T", "J", "Q", "K",
+];
+const CARD_SUITS: [char; 4] = ['C', 'D', 'H', 'S'];
+
+/// Chi-squared critical values at a significance level of 0.1% for 1, 5 and 19 degrees of freedom.
+const CHI_SQUARED_COIN: f64 = 10.828;
+const CHI_SQUARED_D6: f64 = 20.515;
+const CHI_SQUARED_D20: f64 = 43.820;
+/// Minimum expected count of every value before a bias check is meaningful.
+const MIN_EXPECTED_COUNT: usize = 5;
+
+/// Source of entropy for new or extended seeds.
+pub trait EntropySource {
+    /// Returns exactly `bytes` bytes of entropy or `Err` if the source can't provide that much entropy.
+    fn entropy(&mut self, bytes: usize) -> Result<Vec<u8>, Error>;
+}
+
+impl<R> EntropySource for R
+where
+    R: RngCore + CryptoRng,
+{
+    fn entropy(&mut self, bytes: usize) -> Result<Vec<u8>, Error> {
+        let mut entropy = vec![0u8; bytes];
+        self.fill_bytes(&mut entropy);
+
+        Ok(entropy)
+    }
+}
+
+/// Entropy source which XORs the entropy of two sources.
+/// The result is at least as unpredictable as the stronger of both sources.
+pub struct MixedEntropy<A, B> {
+    first: A,
+    second: B,
+}
+
+impl<A, B> MixedEntropy<A, B> {
+    /// Creates a source which mixes the entropy of `first` and `second`.
+    pub fn new(first: A, second: B) -> Self {
+        MixedEntropy { first, second }
+    }
+}
+
+impl<A, B> EntropySource for MixedEntropy<A, B>
+where
+    A: EntropySource,
+    B: EntropySource,
+{
+    fn entropy(&mut self, bytes: usize) -> Result<Vec<u8>, Error> {
+        let mut entropy = self.first.entropy(bytes)?;
+        let other = self.second.entropy(bytes)?;
+        entropy
+            .iter_mut()
+            .zip(other.iter())
+            .for_each(|(a, b)| *a ^= b);
+
+        Ok(entropy)
+    }
+}
+
+/// Text encoding of raw entropy.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EntropyEncoding {
+    /// Hexadecimal string like `7f80`.
+    Hex,
+    /// String of bits like `0111111110000000`.
+    Binary,
+    /// Base64 string like `f4A=`.
+    Base64,
+}
+
+impl EntropyEncoding {
+    /// Returns `entropy` encoded as `self`.
+    pub fn encode(&self, entropy: &[u8]) -> String {
+        match self {
+            EntropyEncoding::Hex => entropy.to_hex(),
+            EntropyEncoding::Binary => entropy.iter().map(|byte| format!("{:08b}", byte)).collect(),
+            EntropyEncoding::Base64 => base64::encode(entropy),
+        }
+    }
+
+    /// Returns the entropy of `encoded`. Whitespace is ignored.
+    pub fn decode(&self, encoded: &str) -> Result<Vec<u8>, Error> {
+        let encoded: String = encoded.chars().filter(|c| !c.is_whitespace()).collect();
+        match self {
+            EntropyEncoding::Hex => Vec::from_hex(&encoded).map_err(|_| Error::InvalidEntropy),
+            EntropyEncoding::Binary => {
+                if encoded.len() % 8 != 0 || encoded.chars().any(|c| c != '0' && c != '1') {
+                    return Err(Error::InvalidEntropy);
+                }
+                (0..encoded.len())
+                    .step_by(8)
+                    .map(|i| {
+                        u8::from_str_radix(&encoded[i..i + 8], 2).map_err(|_| Error::InvalidEntropy)
+                    })
+                    .collect()
+            }
+            EntropyEncoding::Base64 => base64::decode(&encoded).map_err(|_| Error::InvalidEntropy),
+        }
+    }
+}
+
+impl FromStr for EntropyEncoding {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "hex" => Ok(EntropyEncoding::Hex),
+            "binary" => Ok(EntropyEncoding::Binary),
+            "base64" => Ok(EntropyEncoding::Base64),
+            _ => Err(Error::InvalidEntropy),
+        }
+    }
+}
+
+/// Kind of a physical entropy source.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PhysicalSource {
+    /// Six sided die with values from 1 to 6.
+    D6,
+    /// Twenty sided die with values from 1 to 20.
+    D20,
+    /// Coin with values 0 and 1, or `t` (tails) and `h` (heads).
+    Coin,
+    /// Deck of 52 cards like `AS`, `TD` or `10D`, `7H` and `KC`.
+    Cards,
+}
+
+impl PhysicalSource {
+    /// Returns the name of a single value of `self`.
+    pub fn value_name(&self) -> &'static str {
+        match self {
+            PhysicalSource::D6 => "d6 rolls",
+            PhysicalSource::D20 => "d20 rolls",
+            PhysicalSource::Coin => "coin flips",
+            PhysicalSource::Cards => "cards",
+        }
+    }
+
+    /// Returns the number of possible values of a single roll or flip of `self`.
+    fn sides(&self) -> usize {
+        match self {
+            PhysicalSource::D6 => 6,
+            PhysicalSource::D20 => 20,
+            PhysicalSource::Coin => 2,
+            PhysicalSource::Cards => DECK_SIZE,
+        }
+    }
+}
+
+impl FromStr for PhysicalSource {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "d6" => Ok(PhysicalSource::D6),
+            "d20" => Ok(PhysicalSource::D20),
+            "coin" => Ok(PhysicalSource::Coin),
+            "cards" => Ok(PhysicalSource::Cards),
+            _ => Err(Error::InvalidEntropy),
+        }
+    }
+}
+
+/// Entropy of dice rolls, coin flips or the order of a shuffled deck of cards.
+/// The entropy bytes are the SHA256 hash of the canonical string of all values, see [fmt::Display].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PhysicalEntropy {
+    source: PhysicalSource,
+    values: Vec<u8>,
+}
+
+impl PhysicalEntropy {
+    /// Parses the values of a physical `source` from `input`.
+    /// Dice rolls and cards are separated by whitespace or commas, d6 rolls and coin flips may also be written without separators.
+    /// Fails if a value is out of range or a card is drawn twice.
+    pub fn parse(source: PhysicalSource, input: &str) -> Result<Self, Error> {
+        let values = match source {
+            PhysicalSource::D6 => parse_chars(input, |c| match c {
+                '1'..='6' => Some(c as u8 - b'0'),
+                _ => None,
+            })?,
+            PhysicalSource::Coin => parse_chars(input, |c| match c {
+                '0' | 't' | 'T' => Some(0),
+                '1' | 'h' | 'H' => Some(1),
+                _ => None,
+            })?,
+            PhysicalSource::D20 => parse_tokens(input, |token| match token.parse::<u8>() {
+                Ok(value) if (1..=20).contains(&value) => Some(value),
+                _ => None,
+            })?,
+            PhysicalSource::Cards => parse_tokens(input, parse_card)?,
+        };
+
+        // Every card exists only once in a deck
+        if source == PhysicalSource::Cards {
+            let mut drawn = [false; DECK_SIZE];
+            for card in values.iter() {
+                if drawn[*card as usize] {
+                    return Err(Error::InvalidEntropy);
+                }
+                drawn[*card as usize] = true;
+            }
+        }
+
+        Ok(PhysicalEntropy { source, values })
+    }
+
+    /// Returns the physical source of `self`.
+    pub fn source(&self) -> PhysicalSource {
+        self.source
+    }
+
+    /// Returns the number of rolls, flips or cards.
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    /// Returns `true` if there are no rolls, flips or cards.
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    /// Returns the bits of entropy of all values.
+    pub fn bits(&self) -> f64 {
+        self.bits_of(self.values.len())
+    }
+
+    /// Returns how many more rolls, flips or cards are needed for `bytes` bytes of entropy.
+    /// Returns `None` if the source can never provide that much entropy, e.g. a deck of cards for 32 bytes.
+    pub fn values_needed(&self, bytes: usize) -> Option<usize> {
+        let needed_bits = (bytes * 8) as f64;
+        if bytes > sha256::Hash::LEN {
+            return None;
+        }
+
+        let mut count = self.values.len();
+        while self.bits_of(count) < needed_bits {
+            if self.source == PhysicalSource::Cards && count >= DECK_SIZE {
+                return None;
+            }
+            count += 1;
+        }
+
+        Some(count - self.values.len())
+    }
+
+    /// Returns `true` if the frequencies of the rolls or flips are unlikely for a fair die or coin.
+    /// A chi-squared test with a significance level of 0.1% is used, which needs at least 5 expected occurrences of every value.
+    /// Cards are never biased because each card can only be drawn once.
+    pub fn is_biased(&self) -> bool {
+        let critical_value = match self.source {
+            PhysicalSource::D6 => CHI_SQUARED_D6,
+            PhysicalSource::D20 => CHI_SQUARED_D20,
+            PhysicalSource::Coin => CHI_SQUARED_COIN,
+            PhysicalSource::Cards => return false,
+        };
+        let sides = self.source.sides();
+        if self.values.len() < sides * MIN_EXPECTED_COUNT {
+            return false;
+        }
+
+        // Count occurrences of every value
+        let offset = if self.source == PhysicalSource::Coin {
+            0
+        } else {
+            1
+        };
+        let mut counts = vec![0usize; sides];
+        for value in self.values.iter() {
+            counts[(*value - offset) as usize] += 1;
+        }
+
+        let expected = self.values.len() as f64 / sides as f64;
+        let chi_squared: f64 = counts
+            .iter()
+            .map(|count| (*count as f64 - expected).powi(2) / expected)
+            .sum();
+
+        chi_squared > critical_value
+    }
+
+    /// Returns the bits of entropy of the first `count` values.
+    fn bits_of(&self, count: usize) -> f64 {
+        match self.source {
+            PhysicalSource::Cards => (0..count.min(DECK_SIZE))
+                .map(|i| ((DECK_SIZE - i) as f64).log2())
+                .sum(),
+            _ => count as f64 * (self.source.sides() as f64).log2(),
+        }
+    }
+}
+
+impl EntropySource for PhysicalEntropy {
+    /// Returns the first `bytes` bytes of the SHA256 hash of `self`'s canonical string.
+    /// Fails if `self` has less than `bytes` bytes of entropy.
+    fn entropy(&mut self, bytes: usize) -> Result<Vec<u8>, Error> {
+        if self.values_needed(bytes) != Some(0) {
+            return Err(Error::NotEnoughEntropy);
+        }
+        let hash = sha256::Hash::hash(self.to_string().as_bytes());
+
+        Ok(hash[..bytes].to_vec())
+    }
+}
+
+impl fmt::Display for PhysicalEntropy {
+    /// Writes d6 rolls and coin flips as digits without separators, d20 rolls and cards are separated by spaces.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, value) in self.values.iter().enumerate() {
+            match self.source {
+                PhysicalSource::D6 | PhysicalSource::Coin => write!(f, "{}", value)?,
+                PhysicalSource::D20 | PhysicalSource::Cards if i > 0 => write!(f, " ")?,
+                _ => (),
+            }
+            match self.source {
+                PhysicalSource::D20 => write!(f, "{}", value)?,
+                PhysicalSource::Cards => write!(
+                    f,
+                    "{}{}",
+                    CARD_RANKS[*value as usize % CARD_RANKS.len()],
+                    CARD_SUITS[*value as usize / CARD_RANKS.len()]
+                )?,
+                _ => (),
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Parses every character of `input` except whitespace and commas with `parse`.
+fn parse_chars<F>(input: &str, parse: F) -> Result<Vec<u8>, Error>
+where
+    F: Fn(char) -> Option<u8>,
+{
+    input
+        .chars()
+        .filter(|c| !c.is_whitespace() && *c != ',')
+        .map(|c| parse(c).ok_or(Error::InvalidEntropy))
+        .collect()
+}
+
+/// Parses every whitespace or comma separated token of `input` with `parse`.
+fn parse_tokens<F>(input: &str, parse: F) -> Result<Vec<u8>, Error>
+where
+    F: Fn(&str) -> Option<u8>,
+{
+    input
+        .split(|c: char| c.is_whitespace() || c == ',')
+        .filter(|token| !token.is_empty())
+        .map(|token| parse(token).ok_or(Error::InvalidEntropy))
+        .collect()
+}
+
+/// Parses a card like `AS` or `10D` to its index in a sorted deck.
+fn parse_card(card: &str) -> Option<u8> {
+    let card = card.to_uppercase();
+    let suit_char = card.chars().last()?;
+    let rank = match &card[..card.len() - suit_char.len_utf8()] {
+        "10" => "T",
+        rank => rank,
+    };
+    let rank = CARD_RANKS.iter().position(|r| *r == rank)?;
+    let suit = CARD_SUITS.iter().position(|s| *s == suit_char)?;
+
+    Some((suit * CARD_RANKS.len() + rank) as u8)
+}
+
+#[cfg(test)]
+mod tests {
+    use bitcoin::hashes::{sha256, Hash};
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    use crate::entropy::{
+        EntropyEncoding, EntropySource, MixedEntropy, PhysicalEntropy, PhysicalSource,
+    };
+    use crate::Error;
+
+    #[test]
+    fn entropy_encoding_encodes_and_decodes() {
+        let entropy = [0x7f, 0x80, 0x00];
+        let encodings = [
+            (EntropyEncoding::Hex, "7f8000"),
+            (EntropyEncoding::Binary, "011111111000000000000000"),
+            (EntropyEncoding::Base64, "f4AA"),
+        ];
+
+        for (encoding, encoded) in encodings.iter() {
+            assert_eq!(encoding.encode(&entropy), *encoded);
+            assert_eq!(encoding.decode(encoded).unwrap(), entropy);
+        }
+        let result = EntropyEncoding::Binary.decode("01111111 10000000\n00000000");
+        assert_eq!(result.unwrap(), entropy);
+
+        let invalid = [
+            (EntropyEncoding::Hex, "7f8"),
+            (EntropyEncoding::Hex, "7g"),
+            (EntropyEncoding::Binary, "0111111"),
+            (EntropyEncoding::Binary, "01111112"),
+            (EntropyEncoding::Base64, "f4A*"),
+        ];
+        for (encoding, encoded) in invalid.iter() {
+            assert_eq!(encoding.decode(encoded), Err(Error::InvalidEntropy));
+        }
+    }
+
+    #[test]
+    fn physical_entropy_parses_values() {
+        let dice = PhysicalEntropy::parse(PhysicalSource::D6, "1 2,3\n456 61").unwrap();
+        assert_eq!(dice.len(), 8);
+        assert_eq!(dice.to_string(), "12345661");
+
+        let dice = PhysicalEntropy::parse(PhysicalSource::D20, "1 20, 13 7").unwrap();
+        assert_eq!(dice.len(), 4);
+        assert_eq!(dice.to_string(), "1 20 13 7");
+
+        let coins = PhysicalEntropy::parse(PhysicalSource::Coin, "HTht 01").unwrap();
+        assert_eq!(coins.to_string(), "101001");
+
+        let cards = PhysicalEntropy::parse(PhysicalSource::Cards, "AS 10d, tc KH 2c").unwrap();
+        assert_eq!(cards.len(), 5);
+        assert_eq!(cards.to_string(), "AS TD TC KH 2C");
+    }
+
+    #[test]
+    fn physical_entropy_returns_err_when_values_invalid() {
+        let invalid = [
+            (PhysicalSource::D6, "1230"),
+            (PhysicalSource::D6, "127"),
+            (PhysicalSource::D20, "0 5"),
+            (PhysicalSource::D20, "21"),
+            (PhysicalSource::Coin, "HTX"),
+            (PhysicalSource::Cards, "AS 1S"),
+            (PhysicalSource::Cards, "AS KX"),
+            (PhysicalSource::Cards, "AS TD AS"),
+        ];
+
+        for (source, input) in invalid.iter() {
+            let result = PhysicalEntropy::parse(*source, input);
+            assert_eq!(result, Err(Error::InvalidEntropy));
+        }
+    }
+
+    #[test]
+    fn physical_entropy_counts_values_needed() {
+        let dice = PhysicalEntropy::parse(PhysicalSource::D6, "").unwrap();
+        assert_eq!(dice.values_needed(16), Some(50));
+        assert_eq!(dice.values_needed(32), Some(100));
+
+        let dice = PhysicalEntropy::parse(PhysicalSource::D6, &"1".repeat(40)).unwrap();
+        assert_eq!(dice.values_needed(16), Some(10));
+
+        let dice = PhysicalEntropy::parse(PhysicalSource::D20, "").unwrap();
+        assert_eq!(dice.values_needed(32), Some(60));
+
+        let coins = PhysicalEntropy::parse(PhysicalSource::Coin, &"1".repeat(128)).unwrap();
+        assert_eq!(coins.values_needed(16), Some(0));
+        assert_eq!(coins.values_needed(20), Some(32));
+
+        // A full deck has about 225.58 bits of entropy
+        let cards = PhysicalEntropy::parse(PhysicalSource::Cards, "").unwrap();
+        assert_eq!(cards.values_needed(16), Some(25));
+        assert_eq!(cards.values_needed(28), Some(50));
+        assert_eq!(cards.values_needed(32), None);
+    }
+
+    #[test]
+    fn physical_entropy_detects_bias() {
+        // Too few rolls to tell
+        let dice = PhysicalEntropy::parse(PhysicalSource::D6, "111111").unwrap();
+        assert!(!dice.is_biased());
+
+        let dice = PhysicalEntropy::parse(PhysicalSource::D6, &"1".repeat(100)).unwrap();
+        assert!(dice.is_biased());
+
+        let dice = PhysicalEntropy::parse(PhysicalSource::D6, &"123456".repeat(17)).unwrap();
+        assert!(!dice.is_biased());
+
+        let coins = PhysicalEntropy::parse(PhysicalSource::Coin, &"1101".repeat(64)).unwrap();
+        assert!(coins.is_biased());
+
+        let rolls: Vec<String> = (0..100).map(|i| (i % 20 + 1).to_string()).collect();
+        let dice = PhysicalEntropy::parse(PhysicalSource::D20, &rolls.join(" ")).unwrap();
+        assert!(!dice.is_biased());
+    }
+
+    #[test]
+    fn physical_entropy_returns_hash_of_values() {
+        let rolls = "123456".repeat(17);
+        let mut dice = PhysicalEntropy::parse(PhysicalSource::D6, &rolls).unwrap();
+        let expected = sha256::Hash::hash(rolls.as_bytes());
+
+        assert_eq!(dice.entropy(32).unwrap(), expected[..].to_vec());
+        assert_eq!(dice.entropy(16).unwrap(), expected[..16].to_vec());
+
+        // Not enough rolls
+        let mut dice = PhysicalEntropy::parse(PhysicalSource::D6, "123456").unwrap();
+        assert_eq!(dice.entropy(16), Err(Error::NotEnoughEntropy));
+    }
+
+    #[test]
+    fn mixed_entropy_xors_sources() {
+        let mut dice = PhysicalEntropy::parse(PhysicalSource::D6, &"123456".repeat(17)).unwrap();
+        let dice_entropy = dice.entropy(32).unwrap();
+        let rng_entropy = StdRng::seed_from_u64(1).entropy(32).unwrap();
+
+        let mut mixed = MixedEntropy::new(dice, StdRng::seed_from_u64(1));
+        let result = mixed.entropy(32).unwrap();
+
+        for i in 0..32 {
+            assert_eq!(result[i], dice_entropy[i] ^ rng_entropy[i]);
+        }
+    }
+}
@@ -0,0 +1,224 @@
+//! Reverse lookup of the bip85 indexes of child seeds.
+use std::str::FromStr;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::Mutex;
+use std::thread;
+
+use bip39::{Language, Mnemonic};
+use bitcoin::secp256k1::{Secp256k1, SignOnly};
+use bitcoin::util::bip32::ExtendedPrivKey;
+
+use crate::{derive_child_seed, parse_seed, Error, RecoveryTarget, WordCount};
+
+/// Number of indexes a thread checks before reporting progress.
+const CHUNK_SIZE: u64 = 64;
+/// Word counts of child seeds which are searched for keys.
+const WORD_COUNTS: [WordCount; 5] = [
+    WordCount::Words12,
+    WordCount::Words15,
+    WordCount::Words18,
+    WordCount::Words21,
+    WordCount::Words24,
+];
+
+/// Known information about a bip85 child seed whose index is searched.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ChildTarget {
+    /// The child seed itself, which determines the word count and language to search.
+    Seed(Mnemonic),
+    /// Master fingerprint, xpub or address of the child seed without passphrase like in [RecoveryTarget].
+    Keys(RecoveryTarget),
+}
+
+impl FromStr for ChildTarget {
+    type Err = Error;
+
+    /// Parses a seed or anything [RecoveryTarget] can be parsed from.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if let Ok(seed) = parse_seed(s) {
+            return Ok(ChildTarget::Seed(seed));
+        }
+
+        RecoveryTarget::from_str(s)
+            .map(ChildTarget::Keys)
+            .map_err(|_| Error::BadChildTarget)
+    }
+}
+
+impl ChildTarget {
+    /// Returns the word counts and language of child seeds which can match `self`.
+    fn search_space(&self, language: Language) -> Result<(Vec<WordCount>, Language), Error> {
+        match self {
+            ChildTarget::Seed(seed) => {
+                let word_count = WordCount::from_str(&seed.word_count().to_string())?;
+                Ok((vec![word_count], seed.language()))
+            }
+            ChildTarget::Keys(_) => Ok((WORD_COUNTS.to_vec(), language)),
+        }
+    }
+
+    /// Returns `true` if `child` is the seed `self` describes.
+    fn matches(&self, secp: &Secp256k1<SignOnly>, child: &Mnemonic) -> bool {
+        match self {
+            ChildTarget::Seed(seed) => seed == child,
+            // Derivation only fails for invalid keys, which are no match either
+            ChildTarget::Keys(target) => {
+                ExtendedPrivKey::new_master(target.network(), &child.to_seed(""))
+                    .map_err(Error::from)
+                    .and_then(|root| target.matches(secp, &root))
+                    .unwrap_or(false)
+            }
+        }
+    }
+}
+
+/// Finds the bip85 indexes of a child seed described by `target` among the children of a root `xprv` with an index range `[start, end)`.
+/// Seed targets are only searched with their own word count and language.
+/// Key targets are searched across all word counts in `language` and compared with the keys of child seeds without passphrase.
+/// Indexes are checked with `threads` threads, or with one thread per CPU if `threads` is 0.
+/// `progress` is regularly called with the number of checked and total indexes.
+/// Returns all matching child seeds sorted by their index, which is more than one only for fingerprint collisions.
+pub fn find_child_seed<F>(
+    xprv: &ExtendedPrivKey,
+    target: &ChildTarget,
+    language: Language,
+    (start, end): (u32, u32),
+    threads: usize,
+    progress: F,
+) -> Result<Vec<(u32, Mnemonic)>, Error>
+where
+    F: Fn(u64, u64) + Sync,
+{
+    let (word_counts, language) = target.search_space(language)?;
+    if end > 1 << 31 {
+        return Err(Error::Bip85);
+    }
+    let total = end.saturating_sub(start) as u64;
+
+    let threads = match threads {
+        0 => thread::available_parallelism().map_or(1, |threads| threads.get()),
+        threads => threads,
+    };
+    let next = AtomicU64::new(0);
+    let checked = AtomicU64::new(0);
+    let found = Mutex::new(Vec::new());
+    let failure = Mutex::new(None);
+    // Set by the first thread that fails, so the others stop at their next chunk
+    let stop = AtomicBool::new(false);
+
+    thread::scope(|scope| {
+        for _ in 0..threads {
+            scope.spawn(|| {
+                let secp = Secp256k1::signing_only();
+                loop {
+                    if stop.load(Ordering::Relaxed) {
+                        break;
+                    }
+                    let offset = next.fetch_add(CHUNK_SIZE, Ordering::Relaxed);
+                    if offset >= total {
+                        break;
+                    }
+                    let chunk_end = total.min(offset + CHUNK_SIZE);
+
+                    for index in (start as u64 + offset)..(start as u64 + chunk_end) {
+                        let index = index as u32;
+                        for word_count in word_counts.iter() {
+                            match derive_child_seed(&secp, xprv, index, word_count, language) {
+                                Ok(child) if target.matches(&secp, &child) => {
+                                    found.lock().unwrap().push((index, child))
+                                }
+                                Ok(_) => {}
+                                Err(e) => {
+                                    *failure.lock().unwrap() = Some(e);
+                                    stop.store(true, Ordering::Relaxed);
+                                    return;
+                                }
+                            }
+                        }
+                    }
+                    let checked = checked.fetch_add(chunk_end - offset, Ordering::Relaxed)
+                        + chunk_end
+                        - offset;
+                    progress(checked, total);
+                }
+            });
+        }
+    });
+
+    if let Some(e) = failure.into_inner().unwrap() {
+        return Err(e);
+    }
+    let mut found = found.into_inner().unwrap();
+    found.sort_by_key(|(index, child)| (*index, child.word_count()));
+
+    Ok(found)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use bip39::Language;
+    use bitcoin::Network;
+
+    use crate::find::{find_child_seed, ChildTarget};
+    use crate::{derive_child_seeds, derive_fingerprint, derive_root_xprv, Error, WordCount};
+
+    const SEED: &str = "almost talk bulk high steel flush siege intact liberty radar journey bullet little olympic suffer neck clock glad furnace undo outdoor useful feature mobile";
+
+    #[test]
+    fn find_child_seed_finds_seeds() {
+        let xprv = derive_root_xprv(SEED, None, Network::Bitcoin).unwrap();
+        let child = derive_child_seeds(SEED, None, (150, 151), &WordCount::Words18, None).unwrap();
+        let child = &child[0].1;
+
+        let target = ChildTarget::from_str(&child.to_string()).unwrap();
+        let result =
+            find_child_seed(&xprv, &target, Language::English, (0, 200), 4, |_, _| {}).unwrap();
+        assert_eq!(result, vec![(150, child.clone())]);
+
+        let result =
+            find_child_seed(&xprv, &target, Language::English, (0, 150), 0, |_, _| {}).unwrap();
+        assert!(result.is_empty());
+    }
+
+    #[test]
+    fn find_child_seed_finds_keys_across_word_counts() {
+        let xprv = derive_root_xprv(SEED, None, Network::Bitcoin).unwrap();
+        let child = derive_child_seeds(SEED, None, (7, 8), &WordCount::Words15, None).unwrap();
+        let child = &child[0].1;
+        let fingerprint = derive_fingerprint(child.to_string(), None).unwrap();
+
+        let target = ChildTarget::from_str(&fingerprint.to_string()).unwrap();
+        let result =
+            find_child_seed(&xprv, &target, Language::English, (5, 10), 2, |_, _| {}).unwrap();
+        assert_eq!(result, vec![(7, child.clone())]);
+
+        // Keys are only found in the searched language
+        let result =
+            find_child_seed(&xprv, &target, Language::Czech, (5, 10), 2, |_, _| {}).unwrap();
+        assert!(result.is_empty());
+    }
+
+    #[test]
+    fn find_child_seed_returns_err_when_input_invalid() {
+        assert_eq!(
+            ChildTarget::from_str("wagyu beef"),
+            Err(Error::BadChildTarget)
+        );
+
+        let xprv = derive_root_xprv(SEED, None, Network::Bitcoin).unwrap();
+        let target = ChildTarget::from_str("73c5da0a").unwrap();
+        let result = find_child_seed(&xprv, &target, Language::Portuguese, (0, 10), 1, |_, _| {});
+        assert_eq!(result, Err(Error::UnsupportedLanguage));
+        let result = find_child_seed(
+            &xprv,
+            &target,
+            Language::English,
+            (0, 2147483649),
+            1,
+            |_, _| {},
+        );
+        assert_eq!(result, Err(Error::Bip85));
+    }
+}
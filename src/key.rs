@@ -0,0 +1,414 @@
+//! Extended keys in any SLIP-132 encoding.
+use std::fmt;
+use std::str::FromStr;
+
+use bitcoin::secp256k1::Secp256k1;
+use bitcoin::util::base58;
+use bitcoin::util::bip32::{
+    ChainCode, ChildNumber, DerivationPath, ExtendedPrivKey, ExtendedPubKey, Fingerprint,
+};
+use bitcoin::Network;
+use xyzpub::Version;
+
+use crate::{version_for_network, Error};
+
+/// Every SLIP-132 version an extended key can be encoded with.
+const VERSIONS: [Version; 20] = [
+    Version::Xpub,
+    Version::Ypub,
+    Version::Zpub,
+    Version::Tpub,
+    Version::Upub,
+    Version::Vpub,
+    Version::Xprv,
+    Version::Yprv,
+    Version::Zprv,
+    Version::Tprv,
+    Version::Uprv,
+    Version::Vprv,
+    Version::YpubMultisig,
+    Version::ZpubMultisig,
+    Version::UpubMultisig,
+    Version::VpubMultisig,
+    Version::YprvMultisig,
+    Version::ZprvMultisig,
+    Version::UprvMultisig,
+    Version::VprvMultisig,
+];
+/// Length of a decoded extended key.
+const EXTENDED_KEY_LENGTH: usize = 78;
+
+/// Extended private or public key, which can be used instead of a seed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExtendedKey {
+    /// Extended private key, which can derive hardened children and bip85 child seeds.
+    Private(ExtendedPrivKey),
+    /// Extended public key, which can only derive non-hardened children.
+    Public(ExtendedPubKey),
+}
+
+impl ExtendedKey {
+    /// Returns the extended public key of `self`.
+    pub fn xpub(&self) -> ExtendedPubKey {
+        match self {
+            ExtendedKey::Private(xprv) => ExtendedPubKey::from_private(&Secp256k1::new(), xprv),
+            ExtendedKey::Public(xpub) => *xpub,
+        }
+    }
+
+    /// Returns the fingerprint of `self`, which is the master fingerprint if `self` is a root key.
+    pub fn fingerprint(&self) -> Fingerprint {
+        self.xpub().fingerprint()
+    }
+
+    /// Returns the network of `self`.
+    pub fn network(&self) -> Network {
+        match self {
+            ExtendedKey::Private(xprv) => xprv.network,
+            ExtendedKey::Public(xpub) => xpub.network,
+        }
+    }
+
+    /// Returns the depth of `self`, which is 0 for root keys.
+    pub fn depth(&self) -> u8 {
+        match self {
+            ExtendedKey::Private(xprv) => xprv.depth,
+            ExtendedKey::Public(xpub) => xpub.depth,
+        }
+    }
+
+    /// Returns the fingerprint of the parent of `self`, which is `00000000` for root keys.
+    pub fn parent_fingerprint(&self) -> Fingerprint {
+        match self {
+            ExtendedKey::Private(xprv) => xprv.parent_fingerprint,
+            ExtendedKey::Public(xpub) => xpub.parent_fingerprint,
+        }
+    }
+
+    /// Returns the child number `self` was derived with from its parent.
+    pub fn child_number(&self) -> ChildNumber {
+        match self {
+            ExtendedKey::Private(xprv) => xprv.child_number,
+            ExtendedKey::Public(xpub) => xpub.child_number,
+        }
+    }
+
+    /// Returns the chain code of `self`.
+    pub fn chain_code(&self) -> ChainCode {
+        match self {
+            ExtendedKey::Private(xprv) => xprv.chain_code,
+            ExtendedKey::Public(xpub) => xpub.chain_code,
+        }
+    }
+
+    /// Returns `true` if `self` is an extended private key.
+    pub fn is_private(&self) -> bool {
+        matches!(self, ExtendedKey::Private(_))
+    }
+
+    /// Derives the child of `self` at `path`, which is relative to `self`.
+    /// Fails for hardened paths if `self` is an extended public key.
+    pub fn derive(&self, path: &DerivationPath) -> Result<ExtendedKey, Error> {
+        let secp = Secp256k1::new();
+        let child = match self {
+            ExtendedKey::Private(xprv) => ExtendedKey::Private(xprv.derive_priv(&secp, path)?),
+            ExtendedKey::Public(xpub) => ExtendedKey::Public(xpub.derive_pub(&secp, path)?),
+        };
+
+        Ok(child)
+    }
+}
+
+impl FromStr for ExtendedKey {
+    type Err = Error;
+
+    /// Parses an extended key with any SLIP-132 version like xprv, zpub or Vpub.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let version = key_version(s)?;
+        let network = if version_for_network(&version, Network::Bitcoin) == version {
+            Network::Bitcoin
+        } else {
+            Network::Testnet
+        };
+
+        // Replace the version with xprv, xpub, tprv or tpub, which the bitcoin crate can decode
+        let mut data = base58::from_check(s.trim()).map_err(|_| Error::Bip32)?;
+        if is_private_version(&version) {
+            data[..4].copy_from_slice(&version_for_network(&Version::Xprv, network).bytes());
+            Ok(ExtendedKey::Private(ExtendedPrivKey::decode(&data)?))
+        } else {
+            data[..4].copy_from_slice(&version_for_network(&Version::Xpub, network).bytes());
+            Ok(ExtendedKey::Public(ExtendedPubKey::decode(&data)?))
+        }
+    }
+}
+
+impl fmt::Display for ExtendedKey {
+    /// Formats `self` as xprv, xpub, tprv or tpub.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ExtendedKey::Private(xprv) => write!(f, "{}", xprv),
+            ExtendedKey::Public(xpub) => write!(f, "{}", xpub),
+        }
+    }
+}
+
+/// Trait for returning a versioned string of an extended key.
+pub trait VersionedString {
+    /// Returns the base58 check encoding of `self` with the SLIP-132 `version`.
+    /// Fails if `version` is a private version and `self` a public key or vice versa.
+    fn versioned_string(&self, version: &Version) -> Result<String, Error>;
+}
+
+impl VersionedString for ExtendedPubKey {
+    fn versioned_string(&self, version: &Version) -> Result<String, Error> {
+        if is_private_version(version) {
+            return Err(Error::BadVersion);
+        }
+        xyzpub::convert_version(self.to_string(), version).map_err(|_| Error::Bip32)
+    }
+}
+
+impl VersionedString for ExtendedPrivKey {
+    fn versioned_string(&self, version: &Version) -> Result<String, Error> {
+        if !is_private_version(version) {
+            return Err(Error::BadVersion);
+        }
+        xyzpub::convert_version(self.to_string(), version).map_err(|_| Error::Bip32)
+    }
+}
+
+impl VersionedString for ExtendedKey {
+    fn versioned_string(&self, version: &Version) -> Result<String, Error> {
+        match self {
+            ExtendedKey::Private(xprv) => xprv.versioned_string(version),
+            ExtendedKey::Public(xpub) => xpub.versioned_string(version),
+        }
+    }
+}
+
+/// Re-encodes an extended `key` with any SLIP-132 version to `version`, like a zpub to an xpub or Zpub.
+/// Fails if `key` has a bad checksum or is no valid extended key, or if `version` doesn't match the kind of `key`.
+pub fn convert_key_version(key: &str, version: &Version) -> Result<String, Error> {
+    ExtendedKey::from_str(key)?.versioned_string(version)
+}
+
+/// Returns the SLIP-132 version of an extended `key`, which is detected by its version bytes.
+/// Fails if `key` is no base58 check encoded extended key or has unknown version bytes.
+pub fn key_version(key: &str) -> Result<Version, Error> {
+    let data = base58::from_check(key.trim()).map_err(|_| Error::Bip32)?;
+    if data.len() != EXTENDED_KEY_LENGTH {
+        return Err(Error::Bip32);
+    }
+
+    VERSIONS
+        .iter()
+        .find(|version| version.bytes()[..] == data[..4])
+        .cloned()
+        .ok_or(Error::Bip32)
+}
+
+/// Returns the prefix of keys encoded with `version`, like `zpub` or `Vprv`.
+pub fn version_prefix(version: &Version) -> &'static str {
+    match version {
+        Version::Xpub => "xpub",
+        Version::Ypub => "ypub",
+        Version::Zpub => "zpub",
+        Version::Tpub => "tpub",
+        Version::Upub => "upub",
+        Version::Vpub => "vpub",
+        Version::Xprv => "xprv",
+        Version::Yprv => "yprv",
+        Version::Zprv => "zprv",
+        Version::Tprv => "tprv",
+        Version::Uprv => "uprv",
+        Version::Vprv => "vprv",
+        Version::YpubMultisig => "Ypub",
+        Version::ZpubMultisig => "Zpub",
+        Version::UpubMultisig => "Upub",
+        Version::VpubMultisig => "Vpub",
+        Version::YprvMultisig => "Yprv",
+        Version::ZprvMultisig => "Zprv",
+        Version::UprvMultisig => "Uprv",
+        Version::VprvMultisig => "Vprv",
+    }
+}
+
+/// Returns what keys encoded with `version` are used for according to SLIP-132.
+pub fn version_description(version: &Version) -> &'static str {
+    match version {
+        Version::Xpub => "Mainnet public key for P2PKH or P2SH",
+        Version::Ypub => "Mainnet public key for P2WPKH in P2SH",
+        Version::Zpub => "Mainnet public key for P2WPKH",
+        Version::Tpub => "Testnet public key for P2PKH or P2SH",
+        Version::Upub => "Testnet public key for P2WPKH in P2SH",
+        Version::Vpub => "Testnet public key for P2WPKH",
+        Version::Xprv => "Mainnet private key for P2PKH or P2SH",
+        Version::Yprv => "Mainnet private key for P2WPKH in P2SH",
+        Version::Zprv => "Mainnet private key for P2WPKH",
+        Version::Tprv => "Testnet private key for P2PKH or P2SH",
+        Version::Uprv => "Testnet private key for P2WPKH in P2SH",
+        Version::Vprv => "Testnet private key for P2WPKH",
+        Version::YpubMultisig => "Mainnet public key for multi-signature P2WSH in P2SH",
+        Version::ZpubMultisig => "Mainnet public key for multi-signature P2WSH",
+        Version::UpubMultisig => "Testnet public key for multi-signature P2WSH in P2SH",
+        Version::VpubMultisig => "Testnet public key for multi-signature P2WSH",
+        Version::YprvMultisig => "Mainnet private key for multi-signature P2WSH in P2SH",
+        Version::ZprvMultisig => "Mainnet private key for multi-signature P2WSH",
+        Version::UprvMultisig => "Testnet private key for multi-signature P2WSH in P2SH",
+        Version::VprvMultisig => "Testnet private key for multi-signature P2WSH",
+    }
+}
+
+/// Derives the children of an extended `key` at `paths`, which are relative to `key`.
+/// Fails for hardened paths if `key` is an extended public key.
+/// Returns a tuple of the derivation path and its derived key.
+pub fn derive_keys_at_paths(
+    key: &ExtendedKey,
+    paths: &[DerivationPath],
+) -> Result<Vec<(DerivationPath, ExtendedKey)>, Error> {
+    paths
+        .iter()
+        .map(|path| Ok((path.clone(), key.derive(path)?)))
+        .collect()
+}
+
+/// Returns `true` if `version` is the version of an extended private key.
+fn is_private_version(version: &Version) -> bool {
+    matches!(
+        version,
+        Version::Xprv
+            | Version::Yprv
+            | Version::Zprv
+            | Version::Tprv
+            | Version::Uprv
+            | Version::Vprv
+            | Version::YprvMultisig
+            | Version::ZprvMultisig
+            | Version::UprvMultisig
+            | Version::VprvMultisig
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use bitcoin::util::bip32::{ChildNumber, DerivationPath};
+    use bitcoin::Network;
+    use xyzpub::Version;
+
+    use crate::key::{
+        convert_key_version, derive_keys_at_paths, key_version, version_description,
+        version_prefix, ExtendedKey, VersionedString,
+    };
+    use crate::{derive_root_xprv, Error};
+
+    const SEED: &str = "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about";
+    const ZPUB: &str = "zpub6rFR7y4Q2AijBEqTUquhVz398htDFrtymD9xYYfG1m4wAcvPhXNfE3EfH1r1ADqtfSdVCToUG868RvUUkgDKf31mGDtKsAYz2oz2AGutZYs";
+    const XPUB: &str = "xpub6CatWdiZiodmUeTDp8LT5or8nmbKNcuyvz7WyksVFkKB4RHwCD3XyuvPEbvqAQY3rAPshWcMLoP2fMFMKHPJ4ZeZXYVUhLv1VMrjPC7PW6V";
+
+    #[test]
+    fn key_version_returns_version() {
+        assert_eq!(key_version(ZPUB), Ok(Version::Zpub));
+        assert_eq!(key_version(XPUB), Ok(Version::Xpub));
+        assert_eq!(
+            version_description(&Version::Zpub),
+            "Mainnet public key for P2WPKH"
+        );
+        let zpub = xyzpub::convert_version(ZPUB, &Version::VpubMultisig).unwrap();
+        assert_eq!(key_version(&zpub), Ok(Version::VpubMultisig));
+        assert_eq!(key_version("zpub6rFR7y4Q2Aij"), Err(Error::Bip32));
+    }
+
+    #[test]
+    fn extended_key_parses_slip132_versions() {
+        let key = ExtendedKey::from_str(ZPUB).unwrap();
+        assert!(!key.is_private());
+        assert_eq!(key.network(), Network::Bitcoin);
+        assert_eq!(key.depth(), 3);
+        assert_eq!(key.parent_fingerprint().to_string(), "7ef32bdb");
+        assert_eq!(key.child_number(), ChildNumber::Hardened { index: 0 });
+        assert_eq!(
+            key.chain_code().to_string(),
+            "4a53a0ab21b9dc95869c4e92a161194e03c0ef3ff5014ac692f433c4765490fc"
+        );
+        assert_eq!(key.to_string(), XPUB);
+
+        let vpub = xyzpub::convert_version(ZPUB, &Version::Vpub).unwrap();
+        let key = ExtendedKey::from_str(&vpub).unwrap();
+        assert_eq!(key.network(), Network::Testnet);
+
+        let root = derive_root_xprv(SEED, None, Network::Bitcoin).unwrap();
+        let zprv = xyzpub::convert_version(root.to_string(), &Version::Zprv).unwrap();
+        let key = ExtendedKey::from_str(&zprv).unwrap();
+        assert_eq!(key, ExtendedKey::Private(root));
+        assert_eq!(key.fingerprint().to_string(), "73c5da0a");
+
+        // Broken checksum
+        let mut broken = ZPUB.to_string();
+        broken.pop();
+        assert!(ExtendedKey::from_str(&broken).is_err());
+    }
+
+    #[test]
+    fn convert_key_version_converts_versions() {
+        assert_eq!(
+            convert_key_version(ZPUB, &Version::Xpub),
+            Ok(XPUB.to_string())
+        );
+        assert_eq!(
+            convert_key_version(XPUB, &Version::Zpub),
+            Ok(ZPUB.to_string())
+        );
+
+        let multisig = convert_key_version(ZPUB, &Version::UpubMultisig).unwrap();
+        assert!(multisig.starts_with("Upub"));
+        assert_eq!(key_version(&multisig), Ok(Version::UpubMultisig));
+        assert_eq!(version_prefix(&Version::UpubMultisig), "Upub");
+        assert_eq!(
+            convert_key_version(&multisig, &Version::Zpub),
+            Ok(ZPUB.to_string())
+        );
+
+        // Public keys can't get private versions and vice versa
+        assert_eq!(
+            convert_key_version(ZPUB, &Version::Zprv),
+            Err(Error::BadVersion)
+        );
+        let root = derive_root_xprv(SEED, None, Network::Bitcoin).unwrap();
+        assert_eq!(
+            root.versioned_string(&Version::Xpub),
+            Err(Error::BadVersion)
+        );
+        assert!(root
+            .versioned_string(&Version::YprvMultisig)
+            .unwrap()
+            .starts_with("Yprv"));
+
+        // Broken checksum
+        let mut broken = ZPUB.to_string();
+        broken.pop();
+        broken.push('t');
+        assert!(convert_key_version(&broken, &Version::Xpub).is_err());
+    }
+
+    #[test]
+    fn derive_keys_at_paths_derives_children() {
+        let root = derive_root_xprv(SEED, None, Network::Bitcoin).unwrap();
+        let key = ExtendedKey::Private(root);
+        let paths = vec![DerivationPath::from_str("m/84'/0'/0'").unwrap()];
+        let result = derive_keys_at_paths(&key, &paths).unwrap();
+        assert_eq!(result[0].1.xpub().to_string(), XPUB);
+
+        // Public keys only derive non-hardened children
+        let account = ExtendedKey::from_str(XPUB).unwrap();
+        assert!(derive_keys_at_paths(&account, &paths).is_err());
+        let paths = vec![DerivationPath::from_str("m/0/0").unwrap()];
+        let result = derive_keys_at_paths(&account, &paths).unwrap();
+        assert_eq!(
+            result[0].1.xpub().public_key.to_string(),
+            "0330d54fd0dd420a6e5f8d3624f5f3482cae350f79d5f0753bf5beef9c2d91af3c"
+        );
+    }
+}
@@ -2,32 +2,86 @@
 //!
 //! **Note:** The word `seed` is interchangeably used for bip39 mnemonics.
 //!
-//! - Derive bip85 child seeds
+//! - Generate new seeds
+//! - Derive bip85 child seeds and grandchildren along index paths
+//! - Keep a registry of labelled bip85 child seeds
+//! - Find the bip85 indexes of child seeds
+//! - Derive bip85 hex entropy, WIF keys, xprvs, passwords and dice rolls
 //! - Derive bip32 root xpubs and xprvs from seeds
+//! - Derive master fingerprints and key origins
+//! - Derive output descriptors of accounts
+//! - Derive P2PKH, P2SH-P2WPKH, P2WPKH and P2TR receive and change addresses
 //! - Derive account xpubs and xprvs
+//! - Derive child seeds and accounts lazily with iterators over arbitrarily large ranges
+//! - Use xprvs and xpubs with any SLIP-132 version instead of seeds
+//! - Convert extended keys between SLIP-132 versions
+//! - Inspect extended keys and seeds
+//! - Derive xpubs and xprvs at arbitrary derivation paths with range placeholders
+//! - Derive taproot (bip86) accounts and their output keys
 //! - XOR seeds
 //! - Truncate (reduce entropy to keep first n words of a seed)
 //! - Extend (extend entropy to add words to a seed)
+//! - Calculate valid last (checksum) words of incomplete seeds
+//! - Translate seeds to other bip39 wordlists
+//! - Recover seeds with unknown, misspelled or swapped words
+//! - Validate seeds with detailed diagnostics
+//! - Use dice rolls, coin flips or shuffled cards as entropy
+//! - Convert seeds to raw entropy and back
 //!
 use std::str::FromStr;
 
-use bip85::bip39::{self, Mnemonic};
-use bitcoin::secp256k1::Secp256k1;
-use bitcoin::util::bip32::{self, ChildNumber, DerivationPath, ExtendedPrivKey, ExtendedPubKey};
+use bip39::{Language, Mnemonic};
+use bitcoin::secp256k1::{Secp256k1, Signing};
+use bitcoin::util::bip32::{
+    self, ChildNumber, DerivationPath, ExtendedPrivKey, ExtendedPubKey, Fingerprint,
+};
 use bitcoin::Network;
-use rand::{thread_rng, Rng};
-use seed_xor::SeedXor;
 use std::fmt;
 use xyzpub::Version;
 
+mod address;
+mod applications;
+mod descriptor;
+mod entropy;
+mod find;
+mod key;
+mod path;
+mod recover;
+mod registry;
+mod taproot;
+mod validate;
+
+pub use address::{derive_addresses, derive_addresses_from_seed, Chain, DerivedAddress};
+pub use applications::{
+    derive_child_dice, derive_child_hex, derive_child_passwords, derive_child_wifs,
+    derive_child_xprvs, PasswordEncoding,
+};
+pub use descriptor::{derive_descriptors, descriptor_checksum, AccountDescriptors};
+pub use entropy::{EntropyEncoding, EntropySource, MixedEntropy, PhysicalEntropy, PhysicalSource};
+pub use find::{find_child_seed, ChildTarget};
+pub use key::{
+    convert_key_version, derive_keys_at_paths, key_version, version_description, version_prefix,
+    ExtendedKey, VersionedString,
+};
+pub use path::expand_derivation_path;
+pub use recover::{recover_seed, RecoveryTarget, UNKNOWN_WORD};
+pub use registry::{ChildRegistry, RegistryEntry};
+pub use taproot::derive_taproot_output_keys;
+pub use validate::{validate_seed, UnknownWord, ValidationReport};
+
 const ENTROPY_BYTES_24_WORDS: usize = 32;
+const ENTROPY_BYTES_21_WORDS: usize = 28;
 const ENTROPY_BYTES_18_WORDS: usize = 24;
+const ENTROPY_BYTES_15_WORDS: usize = 20;
 const ENTROPY_BYTES_12_WORDS: usize = 16;
 
+/// Bip85 application number for bip39 mnemonics.
+const BIP85_BIP39_APPLICATION: u32 = 39;
+
 /// All errors in this crate.
 #[derive(Debug, PartialEq, Eq)]
 pub enum Error {
-    /// Word count is not 12, 18 or 24.
+    /// Word count is not 12, 15, 18, 21 or 24.
     BadWordCount,
     /// Wrong checksum or unknown words.
     BadSeed,
@@ -39,12 +93,36 @@ pub enum Error {
     WordCountTooHigh,
     /// Word count is lower than expected.
     WordCountTooLow,
+    /// Language has no bip85 language code.
+    UnsupportedLanguage,
+    /// Entropy has a bad length or encoding, or physical entropy like a die roll is out of range or a card is drawn twice.
+    InvalidEntropy,
+    /// Entropy source can't provide enough entropy.
+    NotEnoughEntropy,
+    /// Recovery target is no fingerprint, root or account xpub, or supported address.
+    BadRecoveryTarget,
+    /// Damaged seed has too many candidates to check.
+    TooManyCandidates,
+    /// Script type is not pkh, sh-wpkh, wpkh or tr.
+    BadScriptType,
+    /// Derivation path or one of its range placeholders is malformed or expands to too many paths.
+    BadDerivationPath,
+    /// Version of an extended key is private for a public key or vice versa, or for another network.
+    BadVersion,
+    /// Password encoding is not base64 or base85.
+    BadPasswordEncoding,
+    /// Registry of labelled child seeds is no valid JSON or has unknown values.
+    BadRegistry,
+    /// Label or index is already registered for another child seed.
+    RegistryConflict,
+    /// Child target is no seed, fingerprint, root or account xpub, or supported address.
+    BadChildTarget,
 }
 
 impl fmt::Display for Error {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
-            Self::BadWordCount => write!(f, "Word count needs to be either 12, 18 or 24"),
+            Self::BadWordCount => write!(f, "Word count needs to be either 12, 15, 18, 21 or 24"),
             Self::BadSeed => write!(
                 f,
                 "Seed is invalid because of a bad checksum or unknown words"
@@ -66,6 +144,43 @@ impl fmt::Display for Error {
                     "Word count of seed is lower than expected for the operation"
                 )
             }
+            Self::UnsupportedLanguage => write!(f, "Language is not supported by bip85"),
+            Self::InvalidEntropy => write!(
+                f,
+                "Entropy is invalid because of its length, encoding, out of range values or duplicate cards"
+            ),
+            Self::NotEnoughEntropy => write!(f, "Not enough entropy for the operation"),
+            Self::BadRecoveryTarget => write!(
+                f,
+                "Recovery target needs to be a fingerprint, a root or account xpub, or a P2PKH, P2SH or P2WPKH address"
+            ),
+            Self::TooManyCandidates => write!(f, "Seed has too many candidates to check"),
+            Self::BadScriptType => {
+                write!(f, "Script type needs to be either pkh, sh-wpkh, wpkh or tr")
+            }
+            Self::BadDerivationPath => write!(
+                f,
+                "Derivation path needs to look like m/84h/0h/{{0..5}}h and expand to at most 100000 paths"
+            ),
+            Self::BadVersion => write!(
+                f,
+                "Version needs to be private for extended private keys and public for extended public keys and match their network"
+            ),
+            Self::BadPasswordEncoding => {
+                write!(f, "Password encoding needs to be either base64 or base85")
+            }
+            Self::BadRegistry => write!(
+                f,
+                "Registry needs to be JSON with a fingerprint and labelled bip39 child seeds"
+            ),
+            Self::RegistryConflict => write!(
+                f,
+                "Label or index is already registered for another child seed"
+            ),
+            Self::BadChildTarget => write!(
+                f,
+                "Child needs to be a seed, a fingerprint, a root or account xpub, or a P2PKH, P2SH or P2WPKH address"
+            ),
         }
     }
 }
@@ -74,6 +189,7 @@ impl From<bip39::Error> for Error {
     fn from(e: bip39::Error) -> Self {
         match e {
             bip39::Error::BadWordCount(_) => Self::BadWordCount,
+            bip39::Error::BadEntropyBitCount(_) => Self::InvalidEntropy,
             _ => Self::BadSeed,
         }
     }
@@ -95,12 +211,16 @@ impl From<bip85::Error> for Error {
 }
 
 /// Valid number of words in a mnemonic.
-#[derive(Debug, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum WordCount {
     /// 12 Words
     Words12,
+    /// 15 Words
+    Words15,
     /// 18 Words
     Words18,
+    /// 21 Words
+    Words21,
     /// 24 Words
     Words24,
 }
@@ -110,10 +230,23 @@ impl WordCount {
     pub fn count(&self) -> u8 {
         match self {
             WordCount::Words12 => 12,
+            WordCount::Words15 => 15,
             WordCount::Words18 => 18,
+            WordCount::Words21 => 21,
             WordCount::Words24 => 24,
         }
     }
+
+    /// Returns the number of entropy bytes of a mnemonic with `self` words.
+    pub fn entropy_bytes(&self) -> usize {
+        match self {
+            WordCount::Words12 => ENTROPY_BYTES_12_WORDS,
+            WordCount::Words15 => ENTROPY_BYTES_15_WORDS,
+            WordCount::Words18 => ENTROPY_BYTES_18_WORDS,
+            WordCount::Words21 => ENTROPY_BYTES_21_WORDS,
+            WordCount::Words24 => ENTROPY_BYTES_24_WORDS,
+        }
+    }
 }
 
 impl FromStr for WordCount {
@@ -122,44 +255,261 @@ impl FromStr for WordCount {
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         match s {
             "12" => Ok(WordCount::Words12),
+            "15" => Ok(WordCount::Words15),
             "18" => Ok(WordCount::Words18),
+            "21" => Ok(WordCount::Words21),
             "24" => Ok(WordCount::Words24),
             _ => Err(Error::BadWordCount),
         }
     }
 }
 
+/// Script type of an account, which determines its derivation path and output descriptor.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ScriptType {
+    /// Legacy `pkh()` derived at `m/44'`.
+    Pkh,
+    /// Nested segwit `sh(wpkh())` derived at `m/49'`.
+    ShWpkh,
+    /// Native segwit `wpkh()` derived at `m/84'`.
+    Wpkh,
+    /// Taproot `tr()` derived at `m/86'`.
+    Tr,
+}
+
+impl ScriptType {
+    /// Returns the bip43 purpose of `self`.
+    pub fn purpose(&self) -> u32 {
+        match self {
+            ScriptType::Pkh => 44,
+            ScriptType::ShWpkh => 49,
+            ScriptType::Wpkh => 84,
+            ScriptType::Tr => 86,
+        }
+    }
+
+    /// Returns the script type whose derivation path a SLIP-132 `version` implies, e.g. [ScriptType::Wpkh] for zpubs.
+    /// Fails for multisig versions.
+    pub fn from_version(version: &Version) -> Result<Self, Error> {
+        match version {
+            Version::Xpub | Version::Xprv | Version::Tpub | Version::Tprv => Ok(ScriptType::Pkh),
+            Version::Ypub | Version::Yprv | Version::Upub | Version::Uprv => Ok(ScriptType::ShWpkh),
+            Version::Zpub | Version::Zprv | Version::Vpub | Version::Vprv => Ok(ScriptType::Wpkh),
+            _ => Err(Error::BadScriptType),
+        }
+    }
+
+    /// Returns the derivation path `m/purpose'/coin_type'` of accounts of `self`.
+    /// The coin type is 0 for [Network::Bitcoin] and 1 for all testnets.
+    pub fn coin_path(&self, network: Network) -> Result<DerivationPath, Error> {
+        let coin_type = if network == Network::Bitcoin { 0 } else { 1 };
+        Ok(DerivationPath::from(vec![
+            ChildNumber::from_hardened_idx(self.purpose())?,
+            ChildNumber::from_hardened_idx(coin_type)?,
+        ]))
+    }
+}
+
+impl FromStr for ScriptType {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "pkh" => Ok(ScriptType::Pkh),
+            "sh-wpkh" => Ok(ScriptType::ShWpkh),
+            "wpkh" => Ok(ScriptType::Wpkh),
+            "tr" => Ok(ScriptType::Tr),
+            _ => Err(Error::BadScriptType),
+        }
+    }
+}
+
+/// Generates a new seed in `language` with exactly `word_count` words.
+/// Its entropy is taken from `entropy_source`, like a cryptographically secure random number generator such as [rand::thread_rng]
+/// or [PhysicalEntropy] of dice rolls.
+pub fn generate_seed<E>(
+    word_count: &WordCount,
+    language: Language,
+    entropy_source: &mut E,
+) -> Result<Mnemonic, Error>
+where
+    E: EntropySource + ?Sized,
+{
+    let entropy = entropy_source.entropy(word_count.entropy_bytes())?;
+
+    Ok(Mnemonic::from_entropy_in(language, &entropy)?)
+}
+
+/// Returns the seed in `language` of the raw `entropy`, which needs to be 16, 20, 24, 28 or 32 bytes long.
+pub fn seed_from_entropy(entropy: &[u8], language: Language) -> Result<Mnemonic, Error> {
+    Ok(Mnemonic::from_entropy_in(language, entropy)?)
+}
+
+/// Returns the seed in `language` of the hex encoded `entropy`, which needs to be 16, 20, 24, 28 or 32 bytes long.
+pub fn seed_from_entropy_hex<S>(entropy: S, language: Language) -> Result<Mnemonic, Error>
+where
+    S: AsRef<str>,
+{
+    seed_from_entropy(&EntropyEncoding::Hex.decode(entropy.as_ref())?, language)
+}
+
+/// Returns the raw entropy of `seed`.
+pub fn seed_to_entropy<S>(seed: S) -> Result<Vec<u8>, Error>
+where
+    S: AsRef<str>,
+{
+    Ok(parse_seed(seed)?.to_entropy())
+}
+
 /// Derives child seeds of `seed` with an index range of `[start, end)`. Each seed's word count will be exactly `word_count`.
+/// The optional bip39 `passphrase` is applied to `seed` before deriving.
+/// The child seeds are derived in `language` or in english if `language` is `None`, whatever the language of `seed` is.
 /// Returns list of tuples containing the derived seeds and their indexes.
 pub fn derive_child_seeds<S>(
     seed: S,
-    (start, mut end): (u32, u32),
+    passphrase: Option<&str>,
+    (start, end): (u32, u32),
     word_count: &WordCount,
+    language: Option<Language>,
 ) -> Result<Vec<(u32, Mnemonic)>, Error>
 where
     S: AsRef<str>,
 {
-    if end < start {
-        end = start;
-    }
-    let xprv = derive_root_xprv(seed)?;
-    let secp = bip85::bitcoin::secp256k1::Secp256k1::new();
+    let language = language.unwrap_or(Language::English);
+    let xprv = derive_root_xprv(seed, passphrase, Network::Bitcoin)?;
+
+    derive_child_seeds_from_xprv(&xprv, (start, end), word_count, language)
+}
+
+/// Derives child seeds of a root `xprv` with an index range `[start, end)` and `word_count` words in `language`.
+/// Works like [derive_child_seeds] for keys which are stored without their seed.
+pub fn derive_child_seeds_from_xprv(
+    xprv: &ExtendedPrivKey,
+    (start, end): (u32, u32),
+    word_count: &WordCount,
+    language: Language,
+) -> Result<Vec<(u32, Mnemonic)>, Error> {
+    iter_child_seeds_from_xprv(xprv, (start, end), word_count, language)?.collect()
+}
 
-    let mut result: Vec<(u32, Mnemonic)> = Vec::with_capacity(end as usize - start as usize);
+/// Lazily derives child seeds of `seed` with an index range of `[start, end)` like [derive_child_seeds].
+/// The root key of `seed` is derived once, so arbitrarily large ranges can be consumed one seed at a time.
+pub fn iter_child_seeds<S>(
+    seed: S,
+    passphrase: Option<&str>,
+    (start, end): (u32, u32),
+    word_count: &WordCount,
+    language: Option<Language>,
+) -> Result<impl Iterator<Item = Result<(u32, Mnemonic), Error>>, Error>
+where
+    S: AsRef<str>,
+{
+    let language = language.unwrap_or(Language::English);
+    let xprv = derive_root_xprv(seed, passphrase, Network::Bitcoin)?;
+
+    iter_child_seeds_from_xprv(&xprv, (start, end), word_count, language)
+}
 
-    for i in start..end {
-        let mnemonic = bip85::to_mnemonic(&secp, &xprv, word_count.count() as u32, i)?;
-        result.push((i, mnemonic));
+/// Lazily derives child seeds of a root `xprv` with an index range `[start, end)` like [derive_child_seeds_from_xprv].
+pub fn iter_child_seeds_from_xprv(
+    xprv: &ExtendedPrivKey,
+    (start, end): (u32, u32),
+    word_count: &WordCount,
+    language: Language,
+) -> Result<impl Iterator<Item = Result<(u32, Mnemonic), Error>>, Error> {
+    bip85_language_code(language)?;
+    let secp = Secp256k1::new();
+    let xprv = *xprv;
+    let word_count = *word_count;
+
+    Ok((start..end).map(move |i| {
+        Ok((
+            i,
+            derive_child_seed(&secp, &xprv, i, &word_count, language)?,
+        ))
+    }))
+}
+
+/// Derives the child seed of a root `xprv` at `index` with `word_count` words in `language`.
+pub(crate) fn derive_child_seed<C: Signing>(
+    secp: &Secp256k1<C>,
+    xprv: &ExtendedPrivKey,
+    index: u32,
+    word_count: &WordCount,
+    language: Language,
+) -> Result<Mnemonic, Error> {
+    // bip85::to_mnemonic only supports 12, 18 and 24 words, so the path is built here
+    let path = DerivationPath::from(vec![
+        ChildNumber::from_hardened_idx(BIP85_BIP39_APPLICATION)?,
+        ChildNumber::from_hardened_idx(bip85_language_code(language)?)?,
+        ChildNumber::from_hardened_idx(word_count.count() as u32)?,
+        ChildNumber::from_hardened_idx(index).map_err(|_| Error::Bip85)?,
+    ]);
+    let entropy = bip85::derive(secp, xprv, &path)?;
+
+    Ok(Mnemonic::from_entropy_in(
+        language,
+        &entropy[..word_count.entropy_bytes()],
+    )?)
+}
+
+/// Derives child seeds of `seed` along an index path like `3/7/0`, where each child seed is derived from the previous one.
+/// The optional bip39 `passphrase` is applied to `seed` only, the following child seeds are used without passphrase.
+/// Each seed's word count will be exactly `word_count` and its language like in [derive_child_seeds].
+/// Returns the lineage as tuples of the index, the child seed and its master fingerprint, starting with the child of `seed`.
+pub fn derive_child_seed_lineage<S>(
+    seed: S,
+    passphrase: Option<&str>,
+    indexes: &[u32],
+    word_count: &WordCount,
+    language: Option<Language>,
+) -> Result<Vec<(u32, Mnemonic, Fingerprint)>, Error>
+where
+    S: AsRef<str>,
+{
+    let language = language.unwrap_or(Language::English);
+    let xprv = derive_root_xprv(seed, passphrase, Network::Bitcoin)?;
+
+    derive_child_seed_lineage_from_xprv(&xprv, indexes, word_count, language)
+}
+
+/// Derives child seeds of a root `xprv` along an index path with `word_count` words in `language`.
+/// Works like [derive_child_seed_lineage] for keys which are stored without their seed.
+pub fn derive_child_seed_lineage_from_xprv(
+    xprv: &ExtendedPrivKey,
+    indexes: &[u32],
+    word_count: &WordCount,
+    language: Language,
+) -> Result<Vec<(u32, Mnemonic, Fingerprint)>, Error> {
+    let secp = Secp256k1::new();
+    let mut parent = *xprv;
+    let mut lineage = Vec::with_capacity(indexes.len());
+
+    for &index in indexes {
+        let end = index.checked_add(1).ok_or(Error::Bip85)?;
+        let (_, child) = derive_child_seeds_from_xprv(&parent, (index, end), word_count, language)?
+            .pop()
+            .ok_or(Error::Bip85)?;
+        parent = ExtendedPrivKey::new_master(Network::Bitcoin, &child.to_seed(""))?;
+        lineage.push((index, child, parent.fingerprint(&secp)));
     }
 
-    Ok(result)
+    Ok(lineage)
 }
 
 /// Extends a `seed`'s number of words to the desired length `word_count` by enxtending its entropy.
-/// The returned new seed will start with the same words as `seed`.
-pub fn extend_seed<S>(seed: S, word_count: &WordCount) -> Result<Mnemonic, Error>
+/// The returned new seed is in `language` or in the language of `seed` if `language` is `None`.
+/// If both languages are the same, the new seed will start with the same words as `seed`.
+/// The added entropy is taken from `entropy_source`.
+pub fn extend_seed<S, E>(
+    seed: S,
+    word_count: &WordCount,
+    language: Option<Language>,
+    entropy_source: &mut E,
+) -> Result<Mnemonic, Error>
 where
     S: AsRef<str>,
+    E: EntropySource + ?Sized,
 {
     // Check if seed can be extended
     let parsed_seed = parse_seed(seed)?;
@@ -169,24 +519,22 @@ where
 
     // Determine length of new entropy
     let mut entropy = parsed_seed.to_entropy();
-    let mut rand = thread_rng();
-    let new_entropy_count = match word_count {
-        WordCount::Words12 => 0,
-        WordCount::Words18 => ENTROPY_BYTES_18_WORDS - entropy.len(),
-        WordCount::Words24 => ENTROPY_BYTES_24_WORDS - entropy.len(),
-    };
+    let new_entropy_count = word_count.entropy_bytes() - entropy.len();
 
     // Generate entropy
-    let more_entropy = std::iter::repeat(())
-        .map(|()| rand.gen::<u8>())
-        .take(new_entropy_count);
-    entropy.extend(more_entropy);
+    entropy.extend(entropy_source.entropy(new_entropy_count)?);
 
-    Ok(Mnemonic::from_entropy(&entropy)?)
+    let language = language.unwrap_or_else(|| parsed_seed.language());
+    Ok(Mnemonic::from_entropy_in(language, &entropy)?)
 }
 
 /// Truncates a `seed`'s number of words to `word_count` by truncating its entropy.
-pub fn truncate_seed<S>(seed: S, word_count: &WordCount) -> Result<Mnemonic, Error>
+/// The returned new seed is in `language` or in the language of `seed` if `language` is `None`.
+pub fn truncate_seed<S>(
+    seed: S,
+    word_count: &WordCount,
+    language: Option<Language>,
+) -> Result<Mnemonic, Error>
 where
     S: AsRef<str>,
 {
@@ -198,117 +546,472 @@ where
 
     // Truncate entropy
     let mut entropy = parsed_seed.to_entropy();
-    match word_count {
-        WordCount::Words12 => entropy.truncate(ENTROPY_BYTES_12_WORDS),
-        WordCount::Words18 => entropy.truncate(ENTROPY_BYTES_18_WORDS),
-        WordCount::Words24 => (),
+    entropy.truncate(word_count.entropy_bytes());
+
+    let language = language.unwrap_or_else(|| parsed_seed.language());
+    Ok(Mnemonic::from_entropy_in(language, &entropy)?)
+}
+
+/// Valid last word of a seed which is missing its last word.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LastWord {
+    /// Index of the word in its wordlist. Its last `checksum_bits` bits are the checksum, the others are entropy.
+    pub index: u16,
+    /// Number of checksum bits in `index`.
+    pub checksum_bits: u8,
+    /// Complete seed ending with the word.
+    pub seed: Mnemonic,
+}
+
+impl LastWord {
+    /// Returns the word itself.
+    pub fn word(&self) -> &'static str {
+        self.seed.words().last().unwrap_or_default()
+    }
+
+    /// Returns the number of entropy bits in `index`.
+    pub fn entropy_bits(&self) -> u8 {
+        11 - self.checksum_bits
+    }
+}
+
+/// Returns every valid last word of `partial_seed`, which needs to have 11, 14, 17, 20 or 23 words.
+/// The words of `partial_seed` are in `language` or in the first language which knows all of them if `language` is `None`.
+/// Since the last word of a seed with `n` words contains `n / 3` checksum bits, there are `2^(11 - n / 3)` valid last words.
+pub fn last_words<S>(partial_seed: S, language: Option<Language>) -> Result<Vec<LastWord>, Error>
+where
+    S: AsRef<str>,
+{
+    let word_count = partial_seed.as_ref().split_whitespace().count() + 1;
+    let word_count = WordCount::from_str(&word_count.to_string())?;
+    let languages = match language {
+        Some(language) => vec![language],
+        None => Language::ALL.to_vec(),
+    };
+
+    for language in languages {
+        // Every candidate is checked by parsing the complete seed
+        let last_words: Vec<LastWord> = language
+            .word_list()
+            .iter()
+            .enumerate()
+            .filter_map(|(i, word)| {
+                let seed = format!("{} {}", partial_seed.as_ref(), word);
+                Mnemonic::parse_in(language, seed)
+                    .ok()
+                    .map(|seed| LastWord {
+                        index: i as u16,
+                        checksum_bits: word_count.count() / 3,
+                        seed,
+                    })
+            })
+            .collect();
+        if !last_words.is_empty() {
+            return Ok(last_words);
+        }
     }
 
-    Ok(Mnemonic::from_entropy(&entropy)?)
+    Err(Error::BadSeed)
 }
 
 /// XORs multiple seeds and returns the resulting seed or `None` if `seeds` is empty.
+/// Seeds of different lengths are XORed over the length of the shorter one and the longest seed determines the resulting length.
+/// The returned seed is in `language` or in the language of the first seed if `language` is `None`.
 /// Can fail if a seed is not a valid [bip39::Mnemonic].
-pub fn xor_seeds(seeds: &[&str]) -> Result<Option<Mnemonic>, Error> {
+pub fn xor_seeds(seeds: &[&str], language: Option<Language>) -> Result<Option<Mnemonic>, Error> {
     let mut mnemonics: Vec<Mnemonic> = Vec::with_capacity(seeds.len());
     for seed in seeds {
-        let mnemonic = Mnemonic::from_str(seed)?;
+        let mnemonic = parse_seed(seed)?;
         mnemonics.push(mnemonic);
     }
 
-    Ok(mnemonics.into_iter().reduce(|a, b| a.xor(&b)))
+    let language = match (language, mnemonics.first()) {
+        (Some(language), _) => language,
+        (None, Some(first)) => first.language(),
+        (None, None) => return Ok(None),
+    };
+    let entropy = mnemonics
+        .iter()
+        .map(|mnemonic| mnemonic.to_entropy())
+        .reduce(|mut a, b| {
+            a.iter_mut().zip(b.iter()).for_each(|(a, b)| *a ^= b);
+            if a.len() < b.len() {
+                a.extend(b.iter().skip(a.len()));
+            }
+            a
+        });
+
+    match entropy {
+        Some(entropy) => Ok(Some(Mnemonic::from_entropy_in(language, &entropy)?)),
+        None => Ok(None),
+    }
+}
+
+/// Translates a `seed` to the wordlist of `language` by keeping its entropy.
+/// Note that the translated seed derives different keys than `seed` because keys are derived from the words.
+pub fn translate_seed<S>(seed: S, language: Language) -> Result<Mnemonic, Error>
+where
+    S: AsRef<str>,
+{
+    let parsed_seed = parse_seed(seed)?;
+
+    Ok(Mnemonic::from_entropy_in(
+        language,
+        &parsed_seed.to_entropy(),
+    )?)
+}
+
+/// Returns the language of a `seed`'s words.
+/// If the words of `seed` are part of multiple wordlists, the first language with a valid checksum is returned.
+pub fn detect_language<S>(seed: S) -> Result<Language, Error>
+where
+    S: AsRef<str>,
+{
+    Ok(parse_seed(seed)?.language())
+}
+
+/// Parses a `seed` string to a [bip39::Mnemonic] in `language` or in its detected language if `language` is `None`.
+pub fn parse_seed_in<S>(seed: S, language: Option<Language>) -> Result<Mnemonic, Error>
+where
+    S: AsRef<str>,
+{
+    match language {
+        Some(language) => Ok(Mnemonic::parse_in(language, seed.as_ref())?),
+        None => parse_seed(seed),
+    }
 }
 
 /// Derives account extended public keys of a `seed` with an index range `[start, end)` and the derivation path of `version`.
+/// The optional bip39 `passphrase` is applied to `seed` before deriving.
+/// The coin type of the derivation path is determined by `network`, see [ScriptType::coin_path].
+/// Fails if `version` is no version of `network`, see [version_for_network].
 /// Returns a tuple of the derivation path and its derived xpub.
 pub fn derive_xpubs_from_seed<S>(
     seed: S,
+    passphrase: Option<&str>,
     (start, end): (u32, u32),
     version: &Version,
+    network: Network,
 ) -> Result<Vec<(DerivationPath, ExtendedPubKey)>, Error>
 where
     S: AsRef<str>,
 {
-    let xprvs = derive_xprvs_from_seed(seed, (start, end), version)?;
-    let secp = Secp256k1::new();
-    let xpubs = xprvs
-        .into_iter()
-        .map(move |(i, xprv)| (i, ExtendedPubKey::from_private(&secp, &xprv)))
-        .collect();
+    if version_for_network(version, network) != *version {
+        return Err(Error::BadVersion);
+    }
+    let script_type = ScriptType::from_version(version)?;
 
-    Ok(xpubs)
+    derive_script_xpubs_from_seed(seed, passphrase, (start, end), &script_type, network)
 }
 
 /// Derives account extended private keys of a `seed` with an index range `[start, end)` and the derivation path of `version`.
+/// The optional bip39 `passphrase` is applied to `seed` before deriving.
+/// The coin type of the derivation path is determined by `network`, see [ScriptType::coin_path].
+/// Fails if `version` is no version of `network`, see [version_for_network].
 /// Returns a tuple of the derivation path and its derived xprv.
 pub fn derive_xprvs_from_seed<S>(
     seed: S,
-    (start, mut end): (u32, u32),
+    passphrase: Option<&str>,
+    (start, end): (u32, u32),
     version: &Version,
+    network: Network,
 ) -> Result<Vec<(DerivationPath, ExtendedPrivKey)>, Error>
 where
     S: AsRef<str>,
 {
-    if end < start {
-        end = start;
+    if version_for_network(version, network) != *version {
+        return Err(Error::BadVersion);
     }
+    let script_type = ScriptType::from_version(version)?;
+
+    derive_script_xprvs_from_seed(seed, passphrase, (start, end), &script_type, network)
+}
+
+/// Derives account extended public keys of a `seed` with an index range `[start, end)` at `m/purpose'/coin_type'/index'`.
+/// The purpose is determined by `script_type`, e.g. 84 for [ScriptType::Wpkh] or 86 for [ScriptType::Tr].
+/// The optional bip39 `passphrase` is applied to `seed` before deriving.
+/// The coin type of the derivation path is determined by `network`, see [ScriptType::coin_path].
+/// Returns a tuple of the derivation path and its derived xpub.
+pub fn derive_script_xpubs_from_seed<S>(
+    seed: S,
+    passphrase: Option<&str>,
+    (start, end): (u32, u32),
+    script_type: &ScriptType,
+    network: Network,
+) -> Result<Vec<(DerivationPath, ExtendedPubKey)>, Error>
+where
+    S: AsRef<str>,
+{
+    iter_xpubs_from_seed(seed, passphrase, (start, end), script_type, network)?.collect()
+}
+
+/// Derives account extended private keys of a `seed` with an index range `[start, end)` at `m/purpose'/coin_type'/index'`.
+/// The purpose is determined by `script_type`, e.g. 84 for [ScriptType::Wpkh] or 86 for [ScriptType::Tr].
+/// The optional bip39 `passphrase` is applied to `seed` before deriving.
+/// The coin type of the derivation path is determined by `network`, see [ScriptType::coin_path].
+/// Returns a tuple of the derivation path and its derived xprv.
+pub fn derive_script_xprvs_from_seed<S>(
+    seed: S,
+    passphrase: Option<&str>,
+    (start, end): (u32, u32),
+    script_type: &ScriptType,
+    network: Network,
+) -> Result<Vec<(DerivationPath, ExtendedPrivKey)>, Error>
+where
+    S: AsRef<str>,
+{
+    let master = derive_root_xprv(seed, passphrase, network)?;
+
+    derive_xprvs_from_xprv(&master, (start, end), script_type)
+}
+
+/// Lazily derives account extended public keys of a `seed` with an index range `[start, end)` like [derive_script_xpubs_from_seed].
+/// The key at `m/purpose'/coin_type'` is derived once, so arbitrarily large ranges can be consumed one key at a time.
+pub fn iter_xpubs_from_seed<S>(
+    seed: S,
+    passphrase: Option<&str>,
+    (start, end): (u32, u32),
+    script_type: &ScriptType,
+    network: Network,
+) -> Result<impl Iterator<Item = Result<(DerivationPath, ExtendedPubKey), Error>>, Error>
+where
+    S: AsRef<str>,
+{
+    let master = derive_root_xprv(seed, passphrase, network)?;
+
+    iter_xpubs_from_xprv(&master, (start, end), script_type)
+}
+
+/// Lazily derives account extended private keys of a `seed` with an index range `[start, end)` like [derive_script_xprvs_from_seed].
+/// The key at `m/purpose'/coin_type'` is derived once, so arbitrarily large ranges can be consumed one key at a time.
+pub fn iter_xprvs_from_seed<S>(
+    seed: S,
+    passphrase: Option<&str>,
+    (start, end): (u32, u32),
+    script_type: &ScriptType,
+    network: Network,
+) -> Result<impl Iterator<Item = Result<(DerivationPath, ExtendedPrivKey), Error>>, Error>
+where
+    S: AsRef<str>,
+{
+    let master = derive_root_xprv(seed, passphrase, network)?;
+
+    iter_xprvs_from_xprv(&master, (start, end), script_type)
+}
+
+/// Derives account extended public keys of a root `xprv` with an index range `[start, end)` at `m/purpose'/coin_type'/index'`.
+/// Works like [derive_script_xpubs_from_seed], but the coin type is determined by the network of `xprv`.
+pub fn derive_xpubs_from_xprv(
+    xprv: &ExtendedPrivKey,
+    (start, end): (u32, u32),
+    script_type: &ScriptType,
+) -> Result<Vec<(DerivationPath, ExtendedPubKey)>, Error> {
+    iter_xpubs_from_xprv(xprv, (start, end), script_type)?.collect()
+}
+
+/// Derives account extended private keys of a root `xprv` with an index range `[start, end)` at `m/purpose'/coin_type'/index'`.
+/// Works like [derive_script_xprvs_from_seed], but the coin type is determined by the network of `xprv`.
+pub fn derive_xprvs_from_xprv(
+    xprv: &ExtendedPrivKey,
+    (start, end): (u32, u32),
+    script_type: &ScriptType,
+) -> Result<Vec<(DerivationPath, ExtendedPrivKey)>, Error> {
+    iter_xprvs_from_xprv(xprv, (start, end), script_type)?.collect()
+}
+
+/// Lazily derives account extended public keys of a root `xprv` with an index range `[start, end)` like [derive_xpubs_from_xprv].
+pub fn iter_xpubs_from_xprv(
+    xprv: &ExtendedPrivKey,
+    (start, end): (u32, u32),
+    script_type: &ScriptType,
+) -> Result<impl Iterator<Item = Result<(DerivationPath, ExtendedPubKey), Error>>, Error> {
+    let xprvs = iter_xprvs_from_xprv(xprv, (start, end), script_type)?;
     let secp = Secp256k1::new();
-    let master = derive_root_xprv(seed)?;
-    let path = derivation_path_from_version(version)?;
-    let mut result: Vec<(DerivationPath, ExtendedPrivKey)> =
-        Vec::with_capacity(end as usize - start as usize);
 
-    for i in start..end {
+    Ok(xprvs.map(move |derived| {
+        let (path, xprv) = derived?;
+        Ok((path, ExtendedPubKey::from_private(&secp, &xprv)))
+    }))
+}
+
+/// Lazily derives account extended private keys of a root `xprv` with an index range `[start, end)` like [derive_xprvs_from_xprv].
+/// The key at `m/purpose'/coin_type'` is derived once and accounts are derived from it.
+pub fn iter_xprvs_from_xprv(
+    xprv: &ExtendedPrivKey,
+    (start, end): (u32, u32),
+    script_type: &ScriptType,
+) -> Result<impl Iterator<Item = Result<(DerivationPath, ExtendedPrivKey), Error>>, Error> {
+    let secp = Secp256k1::new();
+    let path = script_type.coin_path(xprv.network)?;
+    let coin_xprv = xprv.derive_priv(&secp, &path)?;
+
+    Ok((start..end).map(move |i| {
         let child = ChildNumber::from_hardened_idx(i)?;
-        let child_path = path.child(child);
-        let derived = master.derive_priv(&secp, &child_path)?;
-        result.push((child_path, derived));
-    }
+        Ok((path.child(child), coin_xprv.ckd_priv(&secp, child)?))
+    }))
+}
+
+/// Derives extended public keys of a `seed` at arbitrary derivation `paths` for `network`, see [expand_derivation_path].
+/// The optional bip39 `passphrase` is applied to `seed` before deriving.
+/// Returns a tuple of the derivation path and its derived xpub.
+pub fn derive_xpubs_at_paths<S>(
+    seed: S,
+    passphrase: Option<&str>,
+    paths: &[DerivationPath],
+    network: Network,
+) -> Result<Vec<(DerivationPath, ExtendedPubKey)>, Error>
+where
+    S: AsRef<str>,
+{
+    let xprvs = derive_xprvs_at_paths(seed, passphrase, paths, network)?;
+    let secp = Secp256k1::new();
 
-    Ok(result)
+    Ok(xprvs
+        .into_iter()
+        .map(|(path, xprv)| (path, ExtendedPubKey::from_private(&secp, &xprv)))
+        .collect())
 }
 
-/// Derives the master public key of a `seed` at the bip32 root.
-pub fn derive_root_xpub<S>(seed: S) -> Result<ExtendedPubKey, Error>
+/// Derives extended private keys of a `seed` at arbitrary derivation `paths` for `network`, see [expand_derivation_path].
+/// The optional bip39 `passphrase` is applied to `seed` before deriving.
+/// Returns a tuple of the derivation path and its derived xprv.
+pub fn derive_xprvs_at_paths<S>(
+    seed: S,
+    passphrase: Option<&str>,
+    paths: &[DerivationPath],
+    network: Network,
+) -> Result<Vec<(DerivationPath, ExtendedPrivKey)>, Error>
 where
     S: AsRef<str>,
 {
-    let xprv = derive_root_xprv(seed)?;
+    let root = derive_root_xprv(seed, passphrase, network)?;
+    let secp = Secp256k1::new();
+
+    paths
+        .iter()
+        .map(|path| Ok((path.clone(), root.derive_priv(&secp, path)?)))
+        .collect()
+}
+
+/// Derives the master public key of a `seed` at the bip32 root for `network`.
+/// The optional bip39 `passphrase` is applied to `seed` before deriving.
+pub fn derive_root_xpub<S>(
+    seed: S,
+    passphrase: Option<&str>,
+    network: Network,
+) -> Result<ExtendedPubKey, Error>
+where
+    S: AsRef<str>,
+{
+    let xprv = derive_root_xprv(seed, passphrase, network)?;
     let secp = Secp256k1::new();
 
     Ok(ExtendedPubKey::from_private(&secp, &xprv))
 }
 
-/// Derives the master private key of a `seed` at the bip32 root.
-pub fn derive_root_xprv<S>(seed: S) -> Result<ExtendedPrivKey, Error>
+/// Derives the bip32 master fingerprint of a `seed`, which is the same for every network.
+/// The optional bip39 `passphrase` is applied to `seed` before deriving.
+pub fn derive_fingerprint<S>(seed: S, passphrase: Option<&str>) -> Result<Fingerprint, Error>
+where
+    S: AsRef<str>,
+{
+    Ok(derive_root_xpub(seed, passphrase, Network::Bitcoin)?.fingerprint())
+}
+
+/// Returns the key origin of a key derived at `path` from a root key with `fingerprint`, like `[73c5da0a/84h/0h/0h]`.
+pub fn key_origin(fingerprint: &Fingerprint, path: &DerivationPath) -> String {
+    let mut origin = format!("[{}", fingerprint);
+    for child in path.as_ref() {
+        match child {
+            ChildNumber::Normal { index } => origin.push_str(&format!("/{}", index)),
+            ChildNumber::Hardened { index } => origin.push_str(&format!("/{}h", index)),
+        }
+    }
+    origin.push(']');
+
+    origin
+}
+
+/// Derives the master private key of a `seed` at the bip32 root for `network`.
+/// The optional bip39 `passphrase` is NFKD normalized before it is applied to `seed`.
+pub fn derive_root_xprv<S>(
+    seed: S,
+    passphrase: Option<&str>,
+    network: Network,
+) -> Result<ExtendedPrivKey, Error>
 where
     S: AsRef<str>,
 {
     let parsed_seed = parse_seed(seed)?;
-    let entropy = parsed_seed.to_seed("");
-    let xprv = ExtendedPrivKey::new_master(Network::Bitcoin, &entropy)?;
+    let entropy = parsed_seed.to_seed(passphrase.unwrap_or(""));
+    let xprv = ExtendedPrivKey::new_master(network, &entropy)?;
 
     Ok(xprv)
 }
 
+/// Returns the counterpart of `version` for `network`.
+/// Mainnet versions like zpub are returned for [Network::Bitcoin] and testnet versions like vpub for
+/// [Network::Testnet], [Network::Signet] and [Network::Regtest].
+pub fn version_for_network(version: &Version, network: Network) -> Version {
+    let is_mainnet = network == Network::Bitcoin;
+    match version {
+        Version::Xpub | Version::Tpub if is_mainnet => Version::Xpub,
+        Version::Xpub | Version::Tpub => Version::Tpub,
+        Version::Ypub | Version::Upub if is_mainnet => Version::Ypub,
+        Version::Ypub | Version::Upub => Version::Upub,
+        Version::Zpub | Version::Vpub if is_mainnet => Version::Zpub,
+        Version::Zpub | Version::Vpub => Version::Vpub,
+        Version::Xprv | Version::Tprv if is_mainnet => Version::Xprv,
+        Version::Xprv | Version::Tprv => Version::Tprv,
+        Version::Yprv | Version::Uprv if is_mainnet => Version::Yprv,
+        Version::Yprv | Version::Uprv => Version::Uprv,
+        Version::Zprv | Version::Vprv if is_mainnet => Version::Zprv,
+        Version::Zprv | Version::Vprv => Version::Vprv,
+        Version::YpubMultisig | Version::UpubMultisig if is_mainnet => Version::YpubMultisig,
+        Version::YpubMultisig | Version::UpubMultisig => Version::UpubMultisig,
+        Version::ZpubMultisig | Version::VpubMultisig if is_mainnet => Version::ZpubMultisig,
+        Version::ZpubMultisig | Version::VpubMultisig => Version::VpubMultisig,
+        Version::YprvMultisig | Version::UprvMultisig if is_mainnet => Version::YprvMultisig,
+        Version::YprvMultisig | Version::UprvMultisig => Version::UprvMultisig,
+        Version::ZprvMultisig | Version::VprvMultisig if is_mainnet => Version::ZprvMultisig,
+        Version::ZprvMultisig | Version::VprvMultisig => Version::VprvMultisig,
+    }
+}
+
+/// Returns `true` if `passphrase` starts or ends with whitespace.
+/// Such whitespace is part of the passphrase and is most likely unintended.
+pub fn has_surrounding_whitespace(passphrase: &str) -> bool {
+    passphrase.trim() != passphrase
+}
+
 /// Parses a `seed` string to a [bip39::Mnemonic].
 fn parse_seed<S>(seed: S) -> Result<Mnemonic, Error>
 where
     S: AsRef<str>,
 {
-    Ok(Mnemonic::from_str(seed.as_ref())?)
+    match Mnemonic::parse(seed.as_ref()) {
+        // Words are part of multiple wordlists, so the first language with a valid checksum is used
+        Err(bip39::Error::AmbiguousLanguages(languages)) => languages
+            .iter()
+            .find_map(|language| Mnemonic::parse_in(language, seed.as_ref()).ok())
+            .ok_or(Error::BadSeed),
+        result => Ok(result?),
+    }
 }
 
-/// Returns the bip32 derivation path of a xpub/xprv version.
-fn derivation_path_from_version(version: &Version) -> Result<DerivationPath, Error> {
-    match version {
-        Version::Xpub | Version::Xprv => Ok(DerivationPath::from_str("m/44h/0h")?),
-        Version::Ypub | Version::Yprv => Ok(DerivationPath::from_str("m/49h/0h")?),
-        Version::Zpub | Version::Zprv => Ok(DerivationPath::from_str("m/84h/0h")?),
-        Version::Tpub | Version::Tprv => Ok(DerivationPath::from_str("m/44h/1h")?),
-        Version::Upub | Version::Uprv => Ok(DerivationPath::from_str("m/49h/1h")?),
-        Version::Vpub | Version::Vprv => Ok(DerivationPath::from_str("m/84h/1h")?),
-        _ => Err(Error::Bip32),
+/// Returns the bip85 language code of `language`.
+fn bip85_language_code(language: Language) -> Result<u32, Error> {
+    match language {
+        Language::English => Ok(0),
+        Language::Japanese => Ok(1),
+        Language::Korean => Ok(2),
+        Language::Spanish => Ok(3),
+        Language::SimplifiedChinese => Ok(4),
+        Language::TraditionalChinese => Ok(5),
+        Language::French => Ok(6),
+        Language::Italian => Ok(7),
+        Language::Czech => Ok(8),
+        Language::Portuguese => Err(Error::UnsupportedLanguage),
     }
 }
 
@@ -316,39 +1019,184 @@ fn derivation_path_from_version(version: &Version) -> Result<DerivationPath, Err
 mod tests {
     use std::str::FromStr;
 
-    use bip85::bitcoin::util::bip32::DerivationPath;
+    use bip39::{Language, Mnemonic};
+    use bip85::bitcoin::util::bip32::{DerivationPath, ExtendedPrivKey, Fingerprint};
+    use bitcoin::hashes::{sha256, Hash};
+    use bitcoin::secp256k1::Secp256k1;
+    use bitcoin::Network;
+    use rand::rngs::StdRng;
+    use rand::{thread_rng, SeedableRng};
     use xyzpub::Version;
 
     use crate::{
-        derivation_path_from_version, derive_child_seeds, derive_root_xprv, derive_root_xpub,
-        derive_xprvs_from_seed, derive_xpubs_from_seed, extend_seed, parse_seed, truncate_seed,
-        xor_seeds, WordCount,
+        derive_child_seed_lineage, derive_child_seed_lineage_from_xprv, derive_child_seeds,
+        derive_child_seeds_from_xprv, derive_fingerprint, derive_root_xprv, derive_root_xpub,
+        derive_script_xprvs_from_seed, derive_script_xpubs_from_seed, derive_xprvs_at_paths,
+        derive_xprvs_from_seed, derive_xpubs_at_paths, derive_xpubs_from_seed,
+        derive_xpubs_from_xprv, detect_language, expand_derivation_path, extend_seed,
+        generate_seed, has_surrounding_whitespace, iter_child_seeds, iter_xprvs_from_seed,
+        iter_xpubs_from_seed, key_origin, last_words, parse_seed, parse_seed_in, seed_from_entropy,
+        seed_from_entropy_hex, seed_to_entropy, translate_seed, truncate_seed, version_for_network,
+        xor_seeds, Error, PhysicalEntropy, PhysicalSource, ScriptType, WordCount,
     };
 
     #[test]
     fn wordcount_count_returns_correct_number() {
         let word_count_12 = WordCount::Words12;
+        let word_count_15 = WordCount::Words15;
         let word_count_18 = WordCount::Words18;
+        let word_count_21 = WordCount::Words21;
         let word_count_24 = WordCount::Words24;
 
         assert_eq!(word_count_12.count(), 12);
+        assert_eq!(word_count_15.count(), 15);
         assert_eq!(word_count_18.count(), 18);
+        assert_eq!(word_count_21.count(), 21);
         assert_eq!(word_count_24.count(), 24);
     }
 
+    #[test]
+    fn wordcount_entropy_bytes_returns_correct_number() {
+        assert_eq!(WordCount::Words12.entropy_bytes(), 16);
+        assert_eq!(WordCount::Words15.entropy_bytes(), 20);
+        assert_eq!(WordCount::Words18.entropy_bytes(), 24);
+        assert_eq!(WordCount::Words21.entropy_bytes(), 28);
+        assert_eq!(WordCount::Words24.entropy_bytes(), 32);
+    }
+
     #[test]
     fn wordcount_from_str_returns_correct_wordcount() {
         let word_count_12 = WordCount::from_str("12").unwrap();
+        let word_count_15 = WordCount::from_str("15").unwrap();
         let word_count_18 = WordCount::from_str("18").unwrap();
+        let word_count_21 = WordCount::from_str("21").unwrap();
         let word_count_24 = WordCount::from_str("24").unwrap();
         let word_count_err = WordCount::from_str("10");
 
         assert_eq!(word_count_12, WordCount::Words12);
+        assert_eq!(word_count_15, WordCount::Words15);
         assert_eq!(word_count_18, WordCount::Words18);
+        assert_eq!(word_count_21, WordCount::Words21);
         assert_eq!(word_count_24, WordCount::Words24);
         assert!(word_count_err.is_err());
     }
 
+    #[test]
+    fn generate_seed_generates_seed() {
+        let word_counts = [
+            WordCount::Words12,
+            WordCount::Words15,
+            WordCount::Words18,
+            WordCount::Words21,
+            WordCount::Words24,
+        ];
+        for word_count in word_counts.iter() {
+            let result = generate_seed(word_count, Language::English, &mut thread_rng()).unwrap();
+            assert_eq!(result.word_count(), word_count.count() as usize);
+            assert_eq!(result.language(), Language::English);
+        }
+
+        // Same entropy source -> same seed
+        let word_count = WordCount::Words24;
+        let result1 =
+            generate_seed(&word_count, Language::Korean, &mut StdRng::seed_from_u64(1)).unwrap();
+        let result2 =
+            generate_seed(&word_count, Language::Korean, &mut StdRng::seed_from_u64(1)).unwrap();
+        let result3 =
+            generate_seed(&word_count, Language::Korean, &mut StdRng::seed_from_u64(2)).unwrap();
+        assert_eq!(result1.language(), Language::Korean);
+        assert_eq!(result1, result2);
+        assert_ne!(result1, result3);
+    }
+
+    #[test]
+    fn generate_seed_uses_physical_entropy() {
+        let rolls = "123456".repeat(17);
+        let mut dice = PhysicalEntropy::parse(PhysicalSource::D6, &rolls).unwrap();
+        let hash = sha256::Hash::hash(rolls.as_bytes());
+
+        let result = generate_seed(&WordCount::Words24, Language::English, &mut dice).unwrap();
+        assert_eq!(result.to_entropy(), hash[..].to_vec());
+        let result = generate_seed(&WordCount::Words12, Language::English, &mut dice).unwrap();
+        assert_eq!(result.to_entropy(), hash[..16].to_vec());
+
+        // 50 rolls are enough for 12 but not for 15 words
+        let mut dice = PhysicalEntropy::parse(PhysicalSource::D6, &rolls[..50]).unwrap();
+        let result = generate_seed(&WordCount::Words12, Language::English, &mut dice);
+        assert!(result.is_ok());
+        let result = generate_seed(&WordCount::Words15, Language::English, &mut dice);
+        assert_eq!(result, Err(Error::NotEnoughEntropy));
+    }
+
+    #[test]
+    fn derive_fingerprint_returns_master_fingerprint() {
+        let seed = "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about";
+        let result = derive_fingerprint(seed, None).unwrap();
+        assert_eq!(result.to_string(), "73c5da0a");
+
+        let result = derive_fingerprint(seed, Some("TREZOR")).unwrap();
+        let expected = derive_root_xpub(seed, Some("TREZOR"), Network::Testnet)
+            .unwrap()
+            .fingerprint();
+        assert_eq!(result, expected);
+    }
+
+    #[test]
+    fn key_origin_returns_fingerprint_and_path() {
+        let fingerprint = Fingerprint::from_str("73c5da0a").unwrap();
+        let path = DerivationPath::from_str("m/84'/0'/0'/1/5").unwrap();
+        assert_eq!(key_origin(&fingerprint, &path), "[73c5da0a/84h/0h/0h/1/5]");
+
+        let path = DerivationPath::from_str("m").unwrap();
+        assert_eq!(key_origin(&fingerprint, &path), "[73c5da0a]");
+    }
+
+    #[test]
+    fn seed_from_entropy_hex_returns_seed() {
+        let result =
+            seed_from_entropy_hex("00000000000000000000000000000000", Language::English).unwrap();
+        assert_eq!(
+            result.to_string(),
+            "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about"
+        );
+
+        let result = seed_from_entropy_hex(
+            "7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f",
+            Language::English,
+        )
+        .unwrap();
+        assert_eq!(
+            result.to_string(),
+            "legal winner thank year wave sausage worth useful legal winner thank year wave sausage worth useful legal winner thank year wave sausage worth title"
+        );
+
+        let result = seed_from_entropy(&[0xff; 20], Language::English).unwrap();
+        assert_eq!(result.word_count(), 15);
+    }
+
+    #[test]
+    fn seed_from_entropy_hex_returns_err_when_entropy_invalid() {
+        let result = seed_from_entropy_hex("0000000000000000000000000000000", Language::English);
+        assert_eq!(result, Err(Error::InvalidEntropy));
+
+        let result = seed_from_entropy_hex("000000000000000000000000000000", Language::English);
+        assert_eq!(result, Err(Error::InvalidEntropy));
+
+        let result = seed_from_entropy_hex("xx000000000000000000000000000000", Language::English);
+        assert_eq!(result, Err(Error::InvalidEntropy));
+    }
+
+    #[test]
+    fn seed_to_entropy_returns_entropy() {
+        let seed =
+            "letter advice cage absurd amount doctor acoustic avoid letter advice cage above";
+        let result = seed_to_entropy(seed).unwrap();
+        assert_eq!(result, [0x80; 16]);
+
+        let result = seed_to_entropy("wagyu beef");
+        assert!(result.is_err());
+    }
+
     #[test]
     fn derive_child_seeds_returns_correct_seeds() {
         let seed = "almost talk bulk high steel flush siege intact liberty radar journey bullet little olympic suffer neck clock glad furnace undo outdoor useful feature mobile";
@@ -357,11 +1205,11 @@ mod tests {
 
         // With 12 Words
         let word_count = WordCount::Words12;
-        let result = derive_child_seeds(seed, (start, end), &word_count).unwrap();
+        let result = derive_child_seeds(seed, None, (start, end), &word_count, None).unwrap();
         let mut expected_index = start;
         let child_seed_0 =
             "loyal utility atom boat debris blush skull rare cool bamboo stage ritual";
-        assert_eq!(result.get(0).unwrap().1.to_string(), child_seed_0);
+        assert_eq!(result.first().unwrap().1.to_string(), child_seed_0);
         for (i, mnemonic) in result {
             assert_eq!(i, expected_index);
             assert_eq!(mnemonic.word_count(), word_count.count() as usize);
@@ -371,7 +1219,7 @@ mod tests {
 
         // With 18 Words
         let word_count = WordCount::Words18;
-        let result = derive_child_seeds(seed, (start, end), &word_count).unwrap();
+        let result = derive_child_seeds(seed, None, (start, end), &word_count, None).unwrap();
         let mut expected_index = start;
         for (i, mnemonic) in result {
             assert_eq!(i, expected_index);
@@ -380,9 +1228,21 @@ mod tests {
         }
         assert_eq!(expected_index, end);
 
+        // With 15 and 21 Words
+        for word_count in [WordCount::Words15, WordCount::Words21].iter() {
+            let result = derive_child_seeds(seed, None, (start, end), word_count, None).unwrap();
+            let mut expected_index = start;
+            for (i, mnemonic) in result {
+                assert_eq!(i, expected_index);
+                assert_eq!(mnemonic.word_count(), word_count.count() as usize);
+                expected_index += 1;
+            }
+            assert_eq!(expected_index, end);
+        }
+
         // With 24 Words
         let word_count = WordCount::Words24;
-        let result = derive_child_seeds(seed, (start, end), &word_count).unwrap();
+        let result = derive_child_seeds(seed, None, (start, end), &word_count, None).unwrap();
         let mut expected_index = start;
         for (i, mnemonic) in result {
             assert_eq!(i, expected_index);
@@ -394,7 +1254,7 @@ mod tests {
         // With start non 0
         let start = 1;
         let word_count = WordCount::Words24;
-        let result = derive_child_seeds(seed, (start, end), &word_count).unwrap();
+        let result = derive_child_seeds(seed, None, (start, end), &word_count, None).unwrap();
         let mut expected_index = start;
         for (i, mnemonic) in result {
             assert_eq!(i, expected_index);
@@ -404,6 +1264,176 @@ mod tests {
         assert_eq!(expected_index, end);
     }
 
+    #[test]
+    fn derive_child_seeds_derives_in_language() {
+        let seed = "almost talk bulk high steel flush siege intact liberty radar journey bullet little olympic suffer neck clock glad furnace undo outdoor useful feature mobile";
+        let word_count = WordCount::Words12;
+        let english = derive_child_seeds(seed, None, (0, 1), &word_count, None).unwrap();
+        let japanese =
+            derive_child_seeds(seed, None, (0, 1), &word_count, Some(Language::Japanese)).unwrap();
+        let (_, english) = english.first().unwrap();
+        let (_, japanese) = japanese.first().unwrap();
+
+        // Language is part of the bip85 derivation path
+        assert_eq!(english.language(), Language::English);
+        assert_eq!(japanese.language(), Language::Japanese);
+        assert_ne!(english.to_entropy(), japanese.to_entropy());
+
+        // English is used by default, also for seeds in other languages
+        for language in [Language::Japanese, Language::Portuguese].iter() {
+            let translated = translate_seed(seed, *language).unwrap().to_string();
+            let result = derive_child_seeds(&translated, None, (0, 1), &word_count, None);
+            let expected = derive_child_seeds(
+                &translated,
+                None,
+                (0, 1),
+                &word_count,
+                Some(Language::English),
+            );
+            assert_eq!(result, expected);
+            assert_eq!(result.unwrap()[0].1.language(), Language::English);
+        }
+
+        // Portuguese has no bip85 language code
+        let result =
+            derive_child_seeds(seed, None, (0, 1), &word_count, Some(Language::Portuguese));
+        assert_eq!(result, Err(Error::UnsupportedLanguage));
+    }
+
+    #[test]
+    fn derive_child_seeds_from_xprv_matches_bip85_vectors() {
+        let xprv = ExtendedPrivKey::from_str("xprv9s21ZrQH143K2LBWUUQRFXhucrQqBpKdRRxNVq2zBqsx8HVqFk2uYo8kmbaLLHRdqtQpUm98uKfu3vca1LqdGhUtyoFnCNkfmXRyPXLjbKb").unwrap();
+        let vectors = [
+            (WordCount::Words12, "girl mad pet galaxy egg matter matrix prison refuse sense ordinary nose"),
+            (WordCount::Words18, "near account window bike charge season chef number sketch tomorrow excuse sniff circle vital hockey outdoor supply token"),
+            (WordCount::Words24, "puppy ocean match cereal symbol another shed magic wrap hammer bulb intact gadget divorce twin tonight reason outdoor destroy simple truth cigar social volcano"),
+        ];
+
+        for (word_count, expected) in vectors.iter() {
+            let result =
+                derive_child_seeds_from_xprv(&xprv, (0, 1), word_count, Language::English).unwrap();
+            assert_eq!(result[0].1.to_string(), *expected);
+        }
+    }
+
+    #[test]
+    fn derive_child_seeds_from_xprv_uses_bip85_language_path() {
+        let xprv = ExtendedPrivKey::from_str("xprv9s21ZrQH143K2LBWUUQRFXhucrQqBpKdRRxNVq2zBqsx8HVqFk2uYo8kmbaLLHRdqtQpUm98uKfu3vca1LqdGhUtyoFnCNkfmXRyPXLjbKb").unwrap();
+        let result =
+            derive_child_seeds_from_xprv(&xprv, (0, 1), &WordCount::Words12, Language::Japanese)
+                .unwrap();
+
+        // bip85::derive prepends 83696968' to the path 39'/1'/12'/0'
+        let path = DerivationPath::from_str("m/39'/1'/12'/0'").unwrap();
+        let entropy = bip85::derive(&Secp256k1::new(), &xprv, &path).unwrap();
+        let expected = Mnemonic::from_entropy_in(Language::Japanese, &entropy[..16]).unwrap();
+        assert_eq!(result[0].1, expected);
+    }
+
+    #[test]
+    fn derive_child_seed_lineage_derives_grandchildren() {
+        let seed = "almost talk bulk high steel flush siege intact liberty radar journey bullet little olympic suffer neck clock glad furnace undo outdoor useful feature mobile";
+        let word_count = WordCount::Words12;
+        let lineage =
+            derive_child_seed_lineage(seed, Some("TREZOR"), &[3, 7, 0], &word_count, None).unwrap();
+        assert_eq!(lineage.len(), 3);
+
+        // Each level is a child of the previous one, only the first with the passphrase
+        let mut parent = seed.to_string();
+        let mut passphrase = Some("TREZOR");
+        for (index, child, fingerprint) in lineage.iter() {
+            let expected =
+                derive_child_seeds(&parent, passphrase, (*index, index + 1), &word_count, None)
+                    .unwrap();
+            assert_eq!(expected[0].1, *child);
+            assert_eq!(
+                *fingerprint,
+                derive_fingerprint(child.to_string(), None).unwrap()
+            );
+            parent = child.to_string();
+            passphrase = None;
+        }
+        assert_eq!(
+            lineage.iter().map(|(i, _, _)| *i).collect::<Vec<u32>>(),
+            vec![3, 7, 0]
+        );
+
+        let xprv = derive_root_xprv(seed, Some("TREZOR"), Network::Testnet).unwrap();
+        let result =
+            derive_child_seed_lineage_from_xprv(&xprv, &[3, 7, 0], &word_count, Language::English)
+                .unwrap();
+        assert_eq!(result, lineage);
+
+        let result = derive_child_seed_lineage(seed, None, &[2147483648], &word_count, None);
+        assert_eq!(result, Err(Error::Bip85));
+    }
+
+    #[test]
+    fn iter_functions_derive_like_vec_functions() {
+        let seed = "almost talk bulk high steel flush siege intact liberty radar journey bullet little olympic suffer neck clock glad furnace undo outdoor useful feature mobile";
+        let word_count = WordCount::Words12;
+
+        let expected = derive_child_seeds(seed, None, (3, 6), &word_count, None).unwrap();
+        let result = iter_child_seeds(seed, None, (3, 6), &word_count, None)
+            .unwrap()
+            .collect::<Result<Vec<_>, Error>>()
+            .unwrap();
+        assert_eq!(result, expected);
+
+        let expected =
+            derive_script_xprvs_from_seed(seed, None, (3, 6), &ScriptType::Wpkh, Network::Testnet)
+                .unwrap();
+        let result = iter_xprvs_from_seed(seed, None, (3, 6), &ScriptType::Wpkh, Network::Testnet)
+            .unwrap()
+            .collect::<Result<Vec<_>, Error>>()
+            .unwrap();
+        assert_eq!(result, expected);
+        assert_eq!(result[0].0.to_string(), "m/84'/1'/3'");
+
+        let expected =
+            derive_script_xpubs_from_seed(seed, None, (3, 6), &ScriptType::Pkh, Network::Bitcoin)
+                .unwrap();
+        let result = iter_xpubs_from_seed(seed, None, (3, 6), &ScriptType::Pkh, Network::Bitcoin)
+            .unwrap()
+            .collect::<Result<Vec<_>, Error>>()
+            .unwrap();
+        assert_eq!(result, expected);
+    }
+
+    #[test]
+    fn iter_functions_derive_lazily() {
+        let seed = "almost talk bulk high steel flush siege intact liberty radar journey bullet little olympic suffer neck clock glad furnace undo outdoor useful feature mobile";
+
+        // Only consumed items are derived, even for the largest possible ranges
+        let result: Vec<u32> =
+            iter_child_seeds(seed, None, (0, u32::MAX), &WordCount::Words24, None)
+                .unwrap()
+                .take(2)
+                .map(|derived| derived.unwrap().0)
+                .collect();
+        assert_eq!(result, vec![0, 1]);
+
+        let mut xpubs = iter_xpubs_from_seed(
+            seed,
+            None,
+            (2147483647, u32::MAX),
+            &ScriptType::Wpkh,
+            Network::Bitcoin,
+        )
+        .unwrap();
+        assert!(xpubs.next().unwrap().is_ok());
+        assert_eq!(xpubs.next().unwrap(), Err(Error::Bip32));
+
+        let result = iter_child_seeds(
+            seed,
+            None,
+            (0, 1),
+            &WordCount::Words12,
+            Some(Language::Portuguese),
+        );
+        assert!(result.is_err());
+    }
+
     #[test]
     fn derive_child_seeds_returns_err_when_seed_invalid() {
         let seed = "almost talk bulk high steel flush siege intact liberty radar";
@@ -411,7 +1441,7 @@ mod tests {
         let end = 9;
         let word_count = WordCount::Words12;
 
-        let result = derive_child_seeds(seed, (start, end), &word_count);
+        let result = derive_child_seeds(seed, None, (start, end), &word_count, None);
 
         assert!(result.is_err());
     }
@@ -422,50 +1452,93 @@ mod tests {
         let seed =
             "tourist correct mango profit mom embody move thought deputy trophy excuse torch";
         let word_count = WordCount::Words12;
-        let result = extend_seed(seed, &word_count).unwrap();
+        let result = extend_seed(seed, &word_count, None, &mut thread_rng()).unwrap();
         assert_eq!(result.to_string(), seed);
 
         // From 12 to 18
         let word_count = WordCount::Words18;
-        let result = extend_seed(seed, &word_count).unwrap();
+        let result = extend_seed(seed, &word_count, None, &mut thread_rng()).unwrap();
         assert_eq!(result.word_count(), 18);
 
         // From 12 to 24
         let word_count = WordCount::Words24;
-        let result = extend_seed(seed, &word_count).unwrap();
+        let result = extend_seed(seed, &word_count, None, &mut thread_rng()).unwrap();
         assert_eq!(result.word_count(), 24);
 
         // From 18 to 12
         let seed = "decline wide tone omit home crime ridge student crop dog purchase actress inject eager hungry country actress shoot";
         let word_count = WordCount::Words12;
-        let result = extend_seed(seed, &word_count);
+        let result = extend_seed(seed, &word_count, None, &mut thread_rng());
         assert!(result.is_err());
 
         // From 18 to 18
         let word_count = WordCount::Words18;
-        let result = extend_seed(seed, &word_count).unwrap();
+        let result = extend_seed(seed, &word_count, None, &mut thread_rng()).unwrap();
         assert_eq!(result.to_string(), seed);
 
         // From 18 to 24
         let word_count = WordCount::Words24;
-        let result = extend_seed(seed, &word_count).unwrap();
+        let result = extend_seed(seed, &word_count, None, &mut thread_rng()).unwrap();
         assert_eq!(result.word_count(), 24);
 
         // From 24 to 12
         let seed = "seven snack chicken they course lawsuit century protect glimpse loan course thing nation ketchup fringe uniform kite else lawn that female impose silver citizen";
         let word_count = WordCount::Words12;
-        let result = extend_seed(seed, &word_count);
+        let result = extend_seed(seed, &word_count, None, &mut thread_rng());
         assert!(result.is_err());
 
         // From 24 to 18
         let word_count = WordCount::Words18;
-        let result = extend_seed(seed, &word_count);
+        let result = extend_seed(seed, &word_count, None, &mut thread_rng());
         assert!(result.is_err());
 
         // From 24 to 24
         let word_count = WordCount::Words24;
-        let result = extend_seed(seed, &word_count).unwrap();
+        let result = extend_seed(seed, &word_count, None, &mut thread_rng()).unwrap();
         assert_eq!(result.to_string(), seed);
+
+        // From 15 to 21
+        let seed = "seven snack chicken they course lawsuit century protect glimpse loan course thing nation ketchup fox";
+        let word_count = WordCount::Words21;
+        let result = extend_seed(seed, &word_count, None, &mut thread_rng()).unwrap();
+        assert_eq!(result.word_count(), 21);
+        assert!(result.to_string().starts_with(&seed[..seed.len() - 4]));
+
+        // From 15 to 12
+        let word_count = WordCount::Words12;
+        let result = extend_seed(seed, &word_count, None, &mut thread_rng());
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn extend_seed_keeps_or_changes_language() {
+        let seed = "ábaco ábaco ábaco ábaco ábaco ábaco ábaco ábaco ábaco ábaco ábaco abierto";
+        let word_count = WordCount::Words24;
+        let result = extend_seed(seed, &word_count, None, &mut thread_rng()).unwrap();
+        assert_eq!(result.language(), Language::Spanish);
+        assert_eq!(result.to_entropy()[..16], [0; 16]);
+
+        let result =
+            extend_seed(seed, &word_count, Some(Language::Czech), &mut thread_rng()).unwrap();
+        assert_eq!(result.language(), Language::Czech);
+        assert_eq!(result.to_entropy()[..16], [0; 16]);
+    }
+
+    #[test]
+    fn extend_seed_uses_physical_entropy() {
+        let seed = "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about";
+        let flips = "1101".repeat(32);
+        let mut coins = PhysicalEntropy::parse(PhysicalSource::Coin, &flips).unwrap();
+        let hash = sha256::Hash::hash(flips.as_bytes());
+
+        let result = extend_seed(seed, &WordCount::Words24, None, &mut coins).unwrap();
+        assert_eq!(result.to_entropy()[..16], [0; 16]);
+        assert_eq!(result.to_entropy()[16..], hash[..16]);
+
+        // 16 more bytes need 128 flips
+        let mut coins = PhysicalEntropy::parse(PhysicalSource::Coin, &flips[..127]).unwrap();
+        let result = extend_seed(seed, &WordCount::Words24, None, &mut coins);
+        assert_eq!(result, Err(Error::NotEnoughEntropy));
     }
 
     #[test]
@@ -474,56 +1547,129 @@ mod tests {
         let seed =
             "tourist correct mango profit mom embody move thought deputy trophy excuse torch";
         let word_count = WordCount::Words12;
-        let result = truncate_seed(seed, &word_count).unwrap();
+        let result = truncate_seed(seed, &word_count, None).unwrap();
         assert_eq!(result.to_string(), seed);
 
         // From 12 to 18 -> err
         let word_count = WordCount::Words18;
-        let result = truncate_seed(seed, &word_count);
+        let result = truncate_seed(seed, &word_count, None);
         assert!(result.is_err());
 
         // From 12 to 24 -> err
         let word_count = WordCount::Words24;
-        let result = truncate_seed(seed, &word_count);
+        let result = truncate_seed(seed, &word_count, None);
         assert!(result.is_err());
 
         // From 18 to 12
         let seed = "decline wide tone omit home crime ridge student crop dog purchase actress inject eager hungry country actress shoot";
         let word_count = WordCount::Words12;
-        let result = truncate_seed(seed, &word_count).unwrap();
+        let result = truncate_seed(seed, &word_count, None).unwrap();
         assert_eq!(result.word_count(), 12);
 
         // From 18 to 18
         let word_count = WordCount::Words18;
-        let result = truncate_seed(seed, &word_count).unwrap();
+        let result = truncate_seed(seed, &word_count, None).unwrap();
         assert_eq!(result.to_string(), seed);
 
         // From 18 to 24 -> err
         let word_count = WordCount::Words24;
-        let result = truncate_seed(seed, &word_count);
+        let result = truncate_seed(seed, &word_count, None);
         assert!(result.is_err());
 
         // From 24 to 12
         let seed = "seven snack chicken they course lawsuit century protect glimpse loan course thing nation ketchup fringe uniform kite else lawn that female impose silver citizen";
         let word_count = WordCount::Words12;
-        let result = truncate_seed(seed, &word_count).unwrap();
+        let result = truncate_seed(seed, &word_count, None).unwrap();
         assert_eq!(result.word_count(), 12);
 
         // From 24 to 18
         let word_count = WordCount::Words18;
-        let result = truncate_seed(seed, &word_count).unwrap();
+        let result = truncate_seed(seed, &word_count, None).unwrap();
         assert_eq!(result.word_count(), 18);
 
         // From 24 to 24
         let word_count = WordCount::Words24;
-        let result = truncate_seed(seed, &word_count).unwrap();
+        let result = truncate_seed(seed, &word_count, None).unwrap();
         assert_eq!(result.to_string(), seed);
+
+        // From 24 to 15
+        let word_count = WordCount::Words15;
+        let expected = "seven snack chicken they course lawsuit century protect glimpse loan course thing nation ketchup fox";
+        let result = truncate_seed(seed, &word_count, None).unwrap();
+        assert_eq!(result.to_string(), expected);
+
+        // From 24 to 21
+        let word_count = WordCount::Words21;
+        let expected = "seven snack chicken they course lawsuit century protect glimpse loan course thing nation ketchup fringe uniform kite else lawn that fit";
+        let result = truncate_seed(seed, &word_count, None).unwrap();
+        assert_eq!(result.to_string(), expected);
+
+        // From 21 to 12
+        let word_count = WordCount::Words12;
+        let result = truncate_seed(expected, &word_count, None).unwrap();
+        assert_eq!(result.word_count(), 12);
+    }
+
+    #[test]
+    fn truncate_seed_keeps_or_changes_language() {
+        let seed = "seven snack chicken they course lawsuit century protect glimpse loan course thing nation ketchup fringe uniform kite else lawn that female impose silver citizen";
+        let japanese = translate_seed(seed, Language::Japanese)
+            .unwrap()
+            .to_string();
+        let word_count = WordCount::Words12;
+
+        let result = truncate_seed(&japanese, &word_count, None).unwrap();
+        assert_eq!(result.language(), Language::Japanese);
+
+        let result = truncate_seed(&japanese, &word_count, Some(Language::English)).unwrap();
+        assert_eq!(
+            result.to_string(),
+            "seven snack chicken they course lawsuit century protect glimpse loan course ticket"
+        );
+    }
+
+    #[test]
+    fn last_words_returns_all_valid_last_words() {
+        let partial_seed = "abandon ".repeat(11);
+        let result = last_words(&partial_seed, None).unwrap();
+        assert_eq!(result.len(), 128);
+        assert_eq!(result[0].word(), "about");
+        assert_eq!(result[0].index, 3);
+        assert_eq!(result[0].checksum_bits, 4);
+        assert_eq!(result[0].entropy_bits(), 7);
+        assert_eq!(result[0].seed.to_entropy(), [0; 16]);
+
+        let partial_seed = "abandon ".repeat(23);
+        let result = last_words(&partial_seed, Some(Language::English)).unwrap();
+        assert_eq!(result.len(), 8);
+        assert_eq!(result[0].word(), "art");
+        assert_eq!(result[0].checksum_bits, 8);
+        assert!(result
+            .iter()
+            .all(|last_word| last_word.seed.word_count() == 24));
+
+        let partial_seed = "ábaco ".repeat(14);
+        let result = last_words(&partial_seed, None).unwrap();
+        assert_eq!(result.len(), 64);
+        assert_eq!(result[0].seed.language(), Language::Spanish);
+    }
+
+    #[test]
+    fn last_words_returns_err_when_seed_invalid() {
+        let result = last_words("abandon ".repeat(12), None);
+        assert_eq!(result, Err(Error::BadWordCount));
+
+        let result = last_words(format!("{} wagyu", "abandon ".repeat(10)), None);
+        assert_eq!(result, Err(Error::BadSeed));
+
+        let result = last_words("abandon ".repeat(11), Some(Language::Czech));
+        assert_eq!(result, Err(Error::BadSeed));
     }
 
     #[test]
     fn xor_seeds_returns_err_when_seed_invalid() {
         let seeds = vec!["wagyu beef"];
-        let result = xor_seeds(&seeds);
+        let result = xor_seeds(&seeds, None);
 
         assert!(result.is_err());
     }
@@ -533,13 +1679,13 @@ mod tests {
         let mut seeds: Vec<&str> = Vec::new();
 
         // No seeds -> None
-        let result = xor_seeds(&seeds).unwrap();
+        let result = xor_seeds(&seeds, None).unwrap();
         assert!(result.is_none());
 
         // One seed -> same seed
         let seed1 = "romance wink lottery autumn shop bring dawn tongue range crater truth ability miss spice fitness easy legal release recall obey exchange recycle dragon room";
         seeds.push(seed1);
-        let result = xor_seeds(&seeds).unwrap().unwrap();
+        let result = xor_seeds(&seeds, None).unwrap().unwrap();
         assert_eq!(result.to_string(), seed1);
 
         // More seeds -> correct XOR
@@ -548,16 +1694,93 @@ mod tests {
         let expected = "silent toe meat possible chair blossom wait occur this worth option bag nurse find fish scene bench asthma bike wage world quit primary indoor";
         seeds.push(seed2);
         seeds.push(seed3);
-        let result = xor_seeds(&seeds).unwrap().unwrap();
+        let result = xor_seeds(&seeds, None).unwrap().unwrap();
         assert_eq!(result.to_string(), expected);
     }
 
+    #[test]
+    fn xor_seeds_keeps_or_changes_language() {
+        let seed1 = "romance wink lottery autumn shop bring dawn tongue range crater truth ability miss spice fitness easy legal release recall obey exchange recycle dragon room";
+        let seed2 = "lion misery divide hurry latin fluid camp advance illegal lab pyramid unaware eager fringe sick camera series noodle toy crowd jeans select depth lounge";
+        let korean = translate_seed(seed1, Language::Korean).unwrap().to_string();
+        let english = xor_seeds(&[seed1, seed2], None).unwrap().unwrap();
+
+        let result = xor_seeds(&[&korean, seed2], None).unwrap().unwrap();
+        assert_eq!(result.language(), Language::Korean);
+        assert_eq!(result.to_entropy(), english.to_entropy());
+
+        let result = xor_seeds(&[&korean, seed2], Some(Language::English))
+            .unwrap()
+            .unwrap();
+        assert_eq!(result, english);
+    }
+
+    #[test]
+    fn xor_seeds_xors_seeds_of_different_lengths() {
+        let seed1 = "romance wink lottery autumn shop bring dawn tongue range crater truth ability miss spice fitness easy legal release recall obey exchange recycle dragon room";
+        let seed2 =
+            "tourist correct mango profit mom embody move thought deputy trophy excuse torch";
+        let long = parse_seed(seed1).unwrap().to_entropy();
+        let short = parse_seed(seed2).unwrap().to_entropy();
+
+        for seeds in [[seed1, seed2], [seed2, seed1]].iter() {
+            let result = xor_seeds(seeds, None).unwrap().unwrap().to_entropy();
+            assert_eq!(result.len(), long.len());
+            for i in 0..short.len() {
+                assert_eq!(result[i], long[i] ^ short[i]);
+            }
+            assert_eq!(result[short.len()..], long[short.len()..]);
+        }
+    }
+
+    #[test]
+    fn translate_seed_keeps_entropy() {
+        let seed = "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about";
+        let expected = "あいこくしん あいこくしん あいこくしん あいこくしん あいこくしん あいこくしん あいこくしん あいこくしん あいこくしん あいこくしん あいこくしん あおぞら";
+        let result = translate_seed(seed, Language::Japanese).unwrap();
+        assert_eq!(result, parse_seed(expected).unwrap());
+
+        let result = translate_seed(expected, Language::English).unwrap();
+        assert_eq!(result.to_string(), seed);
+    }
+
+    #[test]
+    fn detect_language_returns_language() {
+        let seed = "romance wink lottery autumn shop bring dawn tongue range crater truth ability miss spice fitness easy legal release recall obey exchange recycle dragon room";
+        assert_eq!(detect_language(seed).unwrap(), Language::English);
+
+        for language in Language::ALL {
+            let translated = translate_seed(seed, *language).unwrap().to_string();
+            assert_eq!(detect_language(&translated).unwrap(), *language);
+        }
+
+        // Decomposed unicode is normalized
+        let seed = "a\u{301}baco a\u{301}baco a\u{301}baco a\u{301}baco a\u{301}baco a\u{301}baco a\u{301}baco a\u{301}baco a\u{301}baco a\u{301}baco a\u{301}baco abierto";
+        assert_eq!(detect_language(seed).unwrap(), Language::Spanish);
+
+        let seed = "wagyu beef";
+        assert!(detect_language(seed).is_err());
+    }
+
+    #[test]
+    fn parse_seed_in_parses_in_language() {
+        let seed = "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about";
+        let result = parse_seed_in(seed, Some(Language::English)).unwrap();
+        assert_eq!(result.to_string(), seed);
+
+        let result = parse_seed_in(seed, None).unwrap();
+        assert_eq!(result.to_string(), seed);
+
+        let result = parse_seed_in(seed, Some(Language::French));
+        assert!(result.is_err());
+    }
+
     #[test]
     fn derive_root_xprv_derives_root_derives_root_xprv() {
         let seed =
             "artefact enact unable pigeon bottom traffic art antenna country clip inspire borrow";
         let expected = "xprv9s21ZrQH143K3rd3KuNUKxQMNEJsXTxUSuN9RQSm92oJEduoR4wnBneKzSdBDTnv9NtN9VJ2abs66gmM1rNbTdFKHoQPPMeyciwZZqsUbVC";
-        let result = derive_root_xprv(seed).unwrap();
+        let result = derive_root_xprv(seed, None, Network::Bitcoin).unwrap();
         assert_eq!(result.to_string(), expected);
     }
 
@@ -566,10 +1789,51 @@ mod tests {
         let seed =
             "artefact enact unable pigeon bottom traffic art antenna country clip inspire borrow";
         let expected = "xpub661MyMwAqRbcGLhWRvuUh6M5vG9MvvgKp8HkDnrNhNLH7SEwxcG2jaxoqgd5sQf8iQNLMV7F5kSczN52jPqaYRnACAZSjfGuX5sj3AdRDPM";
-        let result = derive_root_xpub(seed).unwrap();
+        let result = derive_root_xpub(seed, None, Network::Bitcoin).unwrap();
         assert_eq!(result.to_string(), expected);
     }
 
+    #[test]
+    fn derive_root_xprv_applies_passphrase() {
+        let seed = "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about";
+        let expected = "xprv9s21ZrQH143K3h3fDYiay8mocZ3afhfULfb5GX8kCBdno77K4HiA15Tg23wpbeF1pLfs1c5SPmYHrEpTuuRhxMwvKDwqdKiGJS9XFKzUsAF";
+        let result = derive_root_xprv(seed, Some("TREZOR"), Network::Bitcoin).unwrap();
+        assert_eq!(result.to_string(), expected);
+
+        // Passphrase is NFKD normalized
+        let composed = derive_root_xprv(seed, Some("caf\u{e9}"), Network::Bitcoin).unwrap();
+        let decomposed = derive_root_xprv(seed, Some("cafe\u{301}"), Network::Bitcoin).unwrap();
+        assert_eq!(composed, decomposed);
+
+        // Empty passphrase is the same as no passphrase
+        let empty = derive_root_xprv(seed, Some(""), Network::Bitcoin).unwrap();
+        let none = derive_root_xprv(seed, None, Network::Bitcoin).unwrap();
+        assert_eq!(empty, none);
+    }
+
+    #[test]
+    fn derive_root_xprv_derives_for_network() {
+        let seed =
+            "artefact enact unable pigeon bottom traffic art antenna country clip inspire borrow";
+        let mainnet = derive_root_xprv(seed, None, Network::Bitcoin).unwrap();
+        let expected = xyzpub::convert_version(mainnet.to_string(), &Version::Tprv).unwrap();
+
+        for network in [Network::Testnet, Network::Signet, Network::Regtest] {
+            let result = derive_root_xprv(seed, None, network).unwrap();
+            assert_eq!(result.network, network);
+            assert_eq!(result.to_string(), expected);
+        }
+    }
+
+    #[test]
+    fn has_surrounding_whitespace_detects_whitespace() {
+        assert!(!has_surrounding_whitespace("correct horse"));
+        assert!(!has_surrounding_whitespace(""));
+        assert!(has_surrounding_whitespace(" correct horse"));
+        assert!(has_surrounding_whitespace("correct horse "));
+        assert!(has_surrounding_whitespace("correct horse\n"));
+    }
+
     #[test]
     fn derive_xprvs_from_seed_derives_xprvs() {
         let seed =
@@ -581,10 +1845,11 @@ mod tests {
         let version = Version::Xprv;
         let expected0 = "xprv9yG8MuRhkRHFKzBVybi9MP13e4xrMYo9hWcp9sUEfAwXcCDNz29CET74FAGwfk6yFceEHpuk5XUrmQnJSJW4dHcmJnwhJj6ee9h2kQUaDz5";
         let expected1 = "xprv9yG8MuRhkRHFPTa4tJbapc9G4QLgfZtqKJ4xsk4p3nsVYDVVERMa9xmiwRPKkpxb9WRJAWakwVja38WRH9FTHbaXcBxsqaT7sk8GzTsKneJ";
-        let result = derive_xprvs_from_seed(seed, (start, end), &version).unwrap();
+        let result =
+            derive_xprvs_from_seed(seed, None, (start, end), &version, Network::Bitcoin).unwrap();
         assert_eq!(result.len(), 9);
-        assert_eq!(result.get(0).unwrap().0.to_string(), "m/44'/0'/0'");
-        assert_eq!(result.get(0).unwrap().1.to_string(), expected0);
+        assert_eq!(result.first().unwrap().0.to_string(), "m/44'/0'/0'");
+        assert_eq!(result.first().unwrap().1.to_string(), expected0);
         assert_eq!(result.get(1).unwrap().0.to_string(), "m/44'/0'/1'");
         assert_eq!(result.get(1).unwrap().1.to_string(), expected1);
 
@@ -592,10 +1857,11 @@ mod tests {
         let version = Version::Yprv;
         let expected0 = "xprv9yvxNCHWSBEQ7AtVCjf2jGK3qHULFkM55EqwcEktzUYLWMy9SiJJ2CTCK24m6sxpim2a7yYY9usaB1nLD6SvkupHCRZz7AE2U8ywMH2jbxU";
         let expected1 = "xprv9yvxNCHWSBEQAqZpE9kUdUu7wbPUSvaC5YP43SyqxRLAHE5HBwe92omAxDMhfZrmV9m2vS46n9xk6JxBwAHq6GfwRto7VnshAwa2bmF33am";
-        let result = derive_xprvs_from_seed(seed, (start, end), &version).unwrap();
+        let result =
+            derive_xprvs_from_seed(seed, None, (start, end), &version, Network::Bitcoin).unwrap();
         assert_eq!(result.len(), 9);
-        assert_eq!(result.get(0).unwrap().0.to_string(), "m/49'/0'/0'");
-        assert_eq!(result.get(0).unwrap().1.to_string(), expected0);
+        assert_eq!(result.first().unwrap().0.to_string(), "m/49'/0'/0'");
+        assert_eq!(result.first().unwrap().1.to_string(), expected0);
         assert_eq!(result.get(1).unwrap().0.to_string(), "m/49'/0'/1'");
         assert_eq!(result.get(1).unwrap().1.to_string(), expected1);
 
@@ -603,14 +1869,55 @@ mod tests {
         let version = Version::Zprv;
         let expected0 = "xprv9zFNLT61T56ccvGNiPh3f1XiWSaGJTwUJYTLvGBdNGfhg2EddRjVwRAUV2LgdiVS5g8ffzUiucZzaZFGcjVjTXsTQGRgndqp5CG6wsG6cvx";
         let expected1 = "xprv9zFNLT61T56cdVw4WVXh5KZFupHAkDXCKTL8oy4WCfznHsafM3wYuCedYQN91v5WYr2LPr2HX3ZrdspypqnXnHjqvNY117FRnKJZfjM3qBF";
-        let result = derive_xprvs_from_seed(seed, (start, end), &version).unwrap();
+        let result =
+            derive_xprvs_from_seed(seed, None, (start, end), &version, Network::Bitcoin).unwrap();
         assert_eq!(result.len(), 9);
-        assert_eq!(result.get(0).unwrap().0.to_string(), "m/84'/0'/0'");
-        assert_eq!(result.get(0).unwrap().1.to_string(), expected0);
+        assert_eq!(result.first().unwrap().0.to_string(), "m/84'/0'/0'");
+        assert_eq!(result.first().unwrap().1.to_string(), expected0);
         assert_eq!(result.get(1).unwrap().0.to_string(), "m/84'/0'/1'");
         assert_eq!(result.get(1).unwrap().1.to_string(), expected1);
     }
 
+    #[test]
+    fn derive_xprvs_from_seed_uses_coin_type_of_network() {
+        let seed =
+            "artefact enact unable pigeon bottom traffic art antenna country clip inspire borrow";
+
+        // Testnet -> coin type 1
+        let result =
+            derive_xprvs_from_seed(seed, None, (0, 2), &Version::Vprv, Network::Testnet).unwrap();
+        assert_eq!(result.first().unwrap().0.to_string(), "m/84'/1'/0'");
+        assert_eq!(result.get(1).unwrap().0.to_string(), "m/84'/1'/1'");
+        assert_eq!(result.first().unwrap().1.network, Network::Testnet);
+
+        // Regtest
+        let result =
+            derive_xprvs_from_seed(seed, None, (0, 1), &Version::Uprv, Network::Regtest).unwrap();
+        assert_eq!(result.first().unwrap().0.to_string(), "m/49'/1'/0'");
+
+        // Versions of other networks are rejected
+        let result = derive_xprvs_from_seed(seed, None, (0, 1), &Version::Zprv, Network::Testnet);
+        assert_eq!(result, Err(Error::BadVersion));
+        let result = derive_xprvs_from_seed(seed, None, (0, 1), &Version::Tprv, Network::Bitcoin);
+        assert_eq!(result, Err(Error::BadVersion));
+        let result = derive_xpubs_from_seed(seed, None, (0, 1), &Version::Tpub, Network::Bitcoin);
+        assert_eq!(result, Err(Error::BadVersion));
+    }
+
+    #[test]
+    fn derive_xpubs_from_seed_derives_testnet_xpubs() {
+        let seed = "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about";
+        let expected = "vpub5Y6cjg78GGuNLsaPhmYsiw4gYX3HoQiRBiSwDaBXKUafCt9bNwWQiitDk5VZ5BVxYnQdwoTyXSs2JHRPAgjAvtbBrf8ZhDYe2jWAqvZVnsc";
+        let result =
+            derive_xpubs_from_seed(seed, None, (0, 1), &Version::Vpub, Network::Testnet).unwrap();
+        let (path, xpub) = result.first().unwrap();
+        assert_eq!(path.to_string(), "m/84'/1'/0'");
+        assert_eq!(
+            xyzpub::convert_version(xpub.to_string(), &Version::Vpub).unwrap(),
+            expected
+        );
+    }
+
     #[test]
     fn derive_xpubs_from_seed_derives_xpubs() {
         let seed =
@@ -622,10 +1929,11 @@ mod tests {
         let version = Version::Xpub;
         let expected0 = "xpub6CFUmQxbanqYYUFy5dF9iWwnC6oLm1X14jYQxFsrDWUWUzYXXZTSnFRY6T9e7V9R1762jkvCHAF7PVQ3rJtC5dwCCA7PkCqoxfrDBhyot63";
         let expected1 = "xpub6CFUmQxbanqYbweXzL8bBk5zcSBB52cggWzZg8URc8QUR1pdmxfphm6CngQSPYbHJopuBLZg7qnMceyfUWN7r5RXeYQKEvArPzkstv1LiBy";
-        let result = derive_xpubs_from_seed(seed, (start, end), &version).unwrap();
+        let result =
+            derive_xpubs_from_seed(seed, None, (start, end), &version, Network::Bitcoin).unwrap();
         assert_eq!(result.len(), 9);
-        assert_eq!(result.get(0).unwrap().0.to_string(), "m/44'/0'/0'");
-        assert_eq!(result.get(0).unwrap().1.to_string(), expected0);
+        assert_eq!(result.first().unwrap().0.to_string(), "m/44'/0'/0'");
+        assert_eq!(result.first().unwrap().1.to_string(), expected0);
         assert_eq!(result.get(1).unwrap().0.to_string(), "m/44'/0'/1'");
         assert_eq!(result.get(1).unwrap().1.to_string(), expected1);
 
@@ -633,10 +1941,11 @@ mod tests {
         let version = Version::Ypub;
         let expected0 = "xpub6CvJmhpQGYnhKexxJmC36QFnPKJpfD4vSTmYQdAWYp5KPAJHzFcYZzmgAJQeMDK57oRiw1cpxVmzadQJDJ9L1LW6cCiWtXvF8jJmqicHeJi";
         let expected1 = "xpub6CvJmhpQGYnhPKeHLBHUzcqrVdDxrPJ3SmJeqqPTWks9A2QRjUxPac5eoV5TtfnhKAQQgKZE377ZmoJc9oe6PSTnP8ETdRTg4tmgARXSUNE";
-        let result = derive_xpubs_from_seed(seed, (start, end), &version).unwrap();
+        let result =
+            derive_xpubs_from_seed(seed, None, (start, end), &version, Network::Bitcoin).unwrap();
         assert_eq!(result.len(), 9);
-        assert_eq!(result.get(0).unwrap().0.to_string(), "m/49'/0'/0'");
-        assert_eq!(result.get(0).unwrap().1.to_string(), expected0);
+        assert_eq!(result.first().unwrap().0.to_string(), "m/49'/0'/0'");
+        assert_eq!(result.first().unwrap().1.to_string(), expected0);
         assert_eq!(result.get(1).unwrap().0.to_string(), "m/49'/0'/1'");
         assert_eq!(result.get(1).unwrap().1.to_string(), expected1);
 
@@ -644,14 +1953,106 @@ mod tests {
         let version = Version::Zpub;
         let expected0 = "xpub6DEijxcuHSeuqQLqpRE429UT4UQkhvfKfmNwiebEvcCgYpZnAy3kVDUxLKqDpPCnho5hjvsoxLB88c3pPXero4YMsNnCeh6jjqhxyA6gT6Q";
         let expected1 = "xpub6DEijxcuHSeuqz1XcX4hSTVzTr7f9gF3ggFjcMU7m1XmAfuotbFoSzy7PhzSPZA9xyYuAysaSrfjuF6caLTa81bAmreaHavVQakAuPKdYQj";
-        let result = derive_xpubs_from_seed(seed, (start, end), &version).unwrap();
+        let result =
+            derive_xpubs_from_seed(seed, None, (start, end), &version, Network::Bitcoin).unwrap();
         assert_eq!(result.len(), 9);
-        assert_eq!(result.get(0).unwrap().0.to_string(), "m/84'/0'/0'");
-        assert_eq!(result.get(0).unwrap().1.to_string(), expected0);
+        assert_eq!(result.first().unwrap().0.to_string(), "m/84'/0'/0'");
+        assert_eq!(result.first().unwrap().1.to_string(), expected0);
         assert_eq!(result.get(1).unwrap().0.to_string(), "m/84'/0'/1'");
         assert_eq!(result.get(1).unwrap().1.to_string(), expected1);
     }
 
+    #[test]
+    fn derive_script_xpubs_from_seed_derives_taproot_xpubs() {
+        let seed = "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about";
+        let expected = "xpub6BgBgsespWvERF3LHQu6CnqdvfEvtMcQjYrcRzx53QJjSxarj2afYWcLteoGVky7D3UKDP9QyrLprQ3VCECoY49yfdDEHGCtMMj92pReUsQ";
+        let result =
+            derive_script_xpubs_from_seed(seed, None, (0, 1), &ScriptType::Tr, Network::Bitcoin)
+                .unwrap();
+        let (path, xpub) = result.first().unwrap();
+        assert_eq!(path.to_string(), "m/86'/0'/0'");
+        assert_eq!(xpub.to_string(), expected);
+    }
+
+    #[test]
+    fn script_type_from_version_returns_script_type() {
+        assert_eq!(
+            ScriptType::from_version(&Version::Xpub),
+            Ok(ScriptType::Pkh)
+        );
+        assert_eq!(
+            ScriptType::from_version(&Version::Uprv),
+            Ok(ScriptType::ShWpkh)
+        );
+        assert_eq!(
+            ScriptType::from_version(&Version::Vpub),
+            Ok(ScriptType::Wpkh)
+        );
+        assert_eq!(
+            ScriptType::from_version(&Version::ZpubMultisig),
+            Err(Error::BadScriptType)
+        );
+    }
+
+    #[test]
+    fn script_type_coin_path_returns_path() {
+        let path = ScriptType::Pkh.coin_path(Network::Bitcoin).unwrap();
+        assert_eq!(path, DerivationPath::from_str("m/44h/0h").unwrap());
+        let path = ScriptType::Wpkh.coin_path(Network::Testnet).unwrap();
+        assert_eq!(path, DerivationPath::from_str("m/84h/1h").unwrap());
+        let path = ScriptType::Tr.coin_path(Network::Regtest).unwrap();
+        assert_eq!(path, DerivationPath::from_str("m/86h/1h").unwrap());
+    }
+
+    #[test]
+    fn derive_xpubs_at_paths_derives_xpubs() {
+        let seed = "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about";
+        let paths = expand_derivation_path("m/84h/0h/{0..2}h").unwrap();
+        let result = derive_xpubs_at_paths(seed, None, &paths, Network::Bitcoin).unwrap();
+        let expected =
+            derive_script_xpubs_from_seed(seed, None, (0, 2), &ScriptType::Wpkh, Network::Bitcoin)
+                .unwrap();
+        assert_eq!(result, expected);
+
+        // Legacy and non-hardened paths
+        let paths = expand_derivation_path("m/0'/0/1").unwrap();
+        let result = derive_xprvs_at_paths(seed, None, &paths, Network::Testnet).unwrap();
+        let (path, xprv) = result.first().unwrap();
+        assert_eq!(path.to_string(), "m/0'/0/1");
+        assert_eq!(xprv.depth, 3);
+        assert_eq!(xprv.network, Network::Testnet);
+    }
+
+    #[test]
+    fn derive_from_xprv_matches_derive_from_seed() {
+        let seed = "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about";
+        let xprv = derive_root_xprv(seed, Some("TREZOR"), Network::Testnet).unwrap();
+
+        let expected = derive_script_xpubs_from_seed(
+            seed,
+            Some("TREZOR"),
+            (0, 2),
+            &ScriptType::ShWpkh,
+            Network::Testnet,
+        )
+        .unwrap();
+        let result = derive_xpubs_from_xprv(&xprv, (0, 2), &ScriptType::ShWpkh).unwrap();
+        assert_eq!(result, expected);
+
+        let expected = derive_child_seeds(
+            seed,
+            Some("TREZOR"),
+            (3, 5),
+            &WordCount::Words18,
+            Some(Language::Czech),
+        )
+        .unwrap();
+        let result =
+            derive_child_seeds_from_xprv(&xprv, (3, 5), &WordCount::Words18, Language::Czech)
+                .unwrap();
+        assert_eq!(result, expected);
+    }
+
     #[test]
     fn parse_seed_returns_mnemonic() {
         let seed =
@@ -660,6 +2061,27 @@ mod tests {
         assert_eq!(result.to_string(), seed);
     }
 
+    #[test]
+    fn parse_seed_returns_mnemonic_with_15_and_21_words() {
+        let seed = "seven snack chicken they course lawsuit century protect glimpse loan course thing nation ketchup fox";
+        let result = parse_seed(seed).unwrap();
+        assert_eq!(result.to_string(), seed);
+
+        let seed = "seven snack chicken they course lawsuit century protect glimpse loan course thing nation ketchup fringe uniform kite else lawn that fit";
+        let result = parse_seed(seed).unwrap();
+        assert_eq!(result.to_string(), seed);
+
+        // Bad checksum
+        let seed = "seven snack chicken they course lawsuit century protect glimpse loan course thing nation ketchup fringe";
+        let result = parse_seed(seed);
+        assert_eq!(result, Err(Error::BadSeed));
+
+        // Unknown word
+        let seed = "seven snack chicken they course lawsuit century protect glimpse loan course thing nation ketchup wagyu";
+        let result = parse_seed(seed);
+        assert_eq!(result, Err(Error::BadSeed));
+    }
+
     #[test]
     fn parse_seed_returns_err_when_seed_invalid() {
         let seed =
@@ -669,77 +2091,28 @@ mod tests {
     }
 
     #[test]
-    fn derivation_path_from_version_returns_path() {
-        let path44 = DerivationPath::from_str("m/44h/0h").unwrap();
-        let path49 = DerivationPath::from_str("m/49h/0h").unwrap();
-        let path84 = DerivationPath::from_str("m/84h/0h").unwrap();
-        let path44_test = DerivationPath::from_str("m/44h/1h").unwrap();
-        let path49_test = DerivationPath::from_str("m/49h/1h").unwrap();
-        let path84_test = DerivationPath::from_str("m/84h/1h").unwrap();
-
-        // xpub
-        let version = Version::Xpub;
-        let path = derivation_path_from_version(&version).unwrap();
-        assert_eq!(path, path44);
-
-        // xprv
-        let version = Version::Xprv;
-        let path = derivation_path_from_version(&version).unwrap();
-        assert_eq!(path, path44);
-
-        // ypub
-        let version = Version::Ypub;
-        let path = derivation_path_from_version(&version).unwrap();
-        assert_eq!(path, path49);
-
-        // yprv
-        let version = Version::Yprv;
-        let path = derivation_path_from_version(&version).unwrap();
-        assert_eq!(path, path49);
-
-        // zpub
-        let version = Version::Zpub;
-        let path = derivation_path_from_version(&version).unwrap();
-        assert_eq!(path, path84);
-
-        // zprv
-        let version = Version::Zprv;
-        let path = derivation_path_from_version(&version).unwrap();
-        assert_eq!(path, path84);
-
-        // tpub
-        let version = Version::Tpub;
-        let path = derivation_path_from_version(&version).unwrap();
-        assert_eq!(path, path44_test);
-
-        // tprv
-        let version = Version::Tprv;
-        let path = derivation_path_from_version(&version).unwrap();
-        assert_eq!(path, path44_test);
-
-        // upub
-        let version = Version::Upub;
-        let path = derivation_path_from_version(&version).unwrap();
-        assert_eq!(path, path49_test);
-
-        // uprv
-        let version = Version::Uprv;
-        let path = derivation_path_from_version(&version).unwrap();
-        assert_eq!(path, path49_test);
-
-        // vpub
-        let version = Version::Vpub;
-        let path = derivation_path_from_version(&version).unwrap();
-        assert_eq!(path, path84_test);
-
-        // vprv
-        let version = Version::Vprv;
-        let path = derivation_path_from_version(&version).unwrap();
-        assert_eq!(path, path84_test);
-
-        // Multisig -> err
-        let version = Version::ZpubMultisig;
-        let path = derivation_path_from_version(&version);
-        assert!(path.is_err());
+    fn version_for_network_returns_counterpart() {
+        let testnets = [Network::Testnet, Network::Signet, Network::Regtest];
+        let pairs = [
+            (Version::Xpub, Version::Tpub),
+            (Version::Ypub, Version::Upub),
+            (Version::Zpub, Version::Vpub),
+            (Version::Xprv, Version::Tprv),
+            (Version::Yprv, Version::Uprv),
+            (Version::Zprv, Version::Vprv),
+            (Version::YpubMultisig, Version::UpubMultisig),
+            (Version::ZpubMultisig, Version::VpubMultisig),
+            (Version::YprvMultisig, Version::UprvMultisig),
+            (Version::ZprvMultisig, Version::VprvMultisig),
+        ];
+
+        for (mainnet, testnet) in pairs.iter() {
+            assert_eq!(version_for_network(mainnet, Network::Bitcoin), *mainnet);
+            assert_eq!(version_for_network(testnet, Network::Bitcoin), *mainnet);
+            for network in testnets.iter() {
+                assert_eq!(version_for_network(mainnet, *network), *testnet);
+                assert_eq!(version_for_network(testnet, *network), *testnet);
+            }
+        }
     }
 }
@@ -0,0 +1,133 @@
+//! Derivation paths with range placeholders.
+use std::str::FromStr;
+
+use bitcoin::util::bip32::{ChildNumber, DerivationPath};
+
+use crate::Error;
+
+/// Maximum number of paths a derivation path with placeholders expands to.
+const MAX_EXPANDED_PATHS: usize = 100_000;
+/// First index of hardened child numbers.
+const HARDENED_OFFSET: u32 = 1 << 31;
+
+/// Expands a derivation `path` like `m/84h/0h/{0..5}h` into all paths of its range placeholders.
+/// A placeholder `{start..end}` stands for every index of `[start, end)` and is hardened by a trailing `h` or `'`.
+/// Paths without placeholders, including non-hardened and legacy paths like `m/0'`, are returned as they are.
+/// Multiple placeholders expand to every combination of their indexes, which may not be more than 100,000 paths.
+pub fn expand_derivation_path(path: &str) -> Result<Vec<DerivationPath>, Error> {
+    let mut parts = path.trim().split('/');
+    if parts.next() != Some("m") {
+        return Err(Error::BadDerivationPath);
+    }
+
+    let mut paths = vec![vec![]];
+    for part in parts {
+        let children = expand_child(part)?;
+        if paths.len() * children.len() > MAX_EXPANDED_PATHS {
+            return Err(Error::BadDerivationPath);
+        }
+        paths = paths
+            .iter()
+            .flat_map(|path: &Vec<ChildNumber>| {
+                children.iter().map(move |child| {
+                    let mut path = path.clone();
+                    path.push(*child);
+                    path
+                })
+            })
+            .collect();
+    }
+
+    Ok(paths.into_iter().map(DerivationPath::from).collect())
+}
+
+/// Expands a single component of a derivation path, which is either a child number or a range placeholder.
+fn expand_child(part: &str) -> Result<Vec<ChildNumber>, Error> {
+    let is_hardened = part.ends_with('h') || part.ends_with('\'');
+    let range = if is_hardened {
+        &part[..part.len() - 1]
+    } else {
+        part
+    };
+
+    let range = match range.strip_prefix('{').and_then(|r| r.strip_suffix('}')) {
+        Some(range) => range,
+        None => {
+            let child = ChildNumber::from_str(part).map_err(|_| Error::BadDerivationPath)?;
+            return Ok(vec![child]);
+        }
+    };
+    let (start, end) = range.split_once("..").ok_or(Error::BadDerivationPath)?;
+    let start = u32::from_str(start).map_err(|_| Error::BadDerivationPath)?;
+    let end = u32::from_str(end).map_err(|_| Error::BadDerivationPath)?;
+    if start >= end || end > HARDENED_OFFSET || (end - start) as usize > MAX_EXPANDED_PATHS {
+        return Err(Error::BadDerivationPath);
+    }
+
+    let children = (start..end)
+        .map(|index| {
+            if is_hardened {
+                ChildNumber::Hardened { index }
+            } else {
+                ChildNumber::Normal { index }
+            }
+        })
+        .collect();
+
+    Ok(children)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::path::expand_derivation_path;
+    use crate::Error;
+
+    fn expanded(path: &str) -> Vec<String> {
+        expand_derivation_path(path)
+            .unwrap()
+            .iter()
+            .map(|p| p.to_string())
+            .collect()
+    }
+
+    #[test]
+    fn expand_derivation_path_returns_plain_paths() {
+        assert_eq!(expanded("m"), vec!["m"]);
+        assert_eq!(expanded("m/0'"), vec!["m/0'"]);
+        assert_eq!(expanded("m/84h/0h/0h/0/7"), vec!["m/84'/0'/0'/0/7"]);
+    }
+
+    #[test]
+    fn expand_derivation_path_expands_placeholders() {
+        assert_eq!(
+            expanded("m/84h/0h/{0..3}h"),
+            vec!["m/84'/0'/0'", "m/84'/0'/1'", "m/84'/0'/2'"]
+        );
+        assert_eq!(
+            expanded("m/{0..2}'/{5..7}"),
+            vec!["m/0'/5", "m/0'/6", "m/1'/5", "m/1'/6"]
+        );
+    }
+
+    #[test]
+    fn expand_derivation_path_fails_for_bad_paths() {
+        let paths = [
+            "84h/0h/0h",
+            "m/{3..3}h",
+            "m/{0..}h",
+            "m/{a..2}",
+            "m/{0-2}",
+            "m/x",
+            "m/2147483648h",
+            "m/{0..2147483649}h",
+            "m/{0..1000}/{0..1000}",
+        ];
+        for path in paths.iter() {
+            assert!(expand_derivation_path(path).is_err(), "{}", path);
+        }
+        assert_eq!(
+            expand_derivation_path("m/{2..1}"),
+            Err(Error::BadDerivationPath)
+        );
+    }
+}
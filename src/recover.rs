@@ -0,0 +1,572 @@
+//! Recovery of damaged seeds with unknown, misspelled or swapped words.
+use std::str::FromStr;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+use std::thread;
+
+use bip39::{Language, Mnemonic};
+use bitcoin::hashes::{sha256, Hash};
+use bitcoin::secp256k1::{Secp256k1, SignOnly};
+use bitcoin::util::address::AddressType;
+use bitcoin::util::bip32::{
+    ChildNumber, DerivationPath, ExtendedPrivKey, ExtendedPubKey, Fingerprint,
+};
+use bitcoin::{Address, Network};
+use unicode_normalization::UnicodeNormalization;
+
+use crate::{key_version, Error, ExtendedKey, ScriptType};
+
+/// Placeholder for an unknown word of a damaged seed.
+pub const UNKNOWN_WORD: &str = "?";
+
+const WORDLIST_SIZE: u16 = 2048;
+/// Misspelled words are replaced by words with at most this edit distance.
+const MAX_EDIT_DISTANCE: usize = 2;
+/// Misspelled words are also replaced by words with the same first letters, which are unique in most wordlists.
+const PREFIX_LENGTH: usize = 4;
+/// Number of receive addresses checked for an address target.
+const ADDRESS_GAP: u32 = 20;
+/// Number of candidates a thread checks before reporting progress.
+const CHUNK_SIZE: u64 = 4096;
+/// Maximum number of candidates checked for a target, about two unknown words and a misspelled one.
+const MAX_CANDIDATES: u64 = 1 << 32;
+/// Maximum number of candidates without a target, two unknown words, as every candidate with a valid checksum is returned.
+const MAX_CANDIDATES_WITHOUT_TARGET: u64 = 1 << 22;
+
+/// Known information about a seed that narrows down recovered candidates.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RecoveryTarget {
+    /// Master fingerprint of the seed.
+    Fingerprint(Fingerprint),
+    /// Root xpub or account xpub at the derivation path of its SLIP-132 version.
+    Xpub(ExtendedPubKey, DerivationPath),
+    /// One of the first 20 receive addresses of the first account.
+    /// Legacy addresses are derived at `m/44'`, P2SH addresses at `m/49'` and native segwit addresses at `m/84'`.
+    Address(Address),
+}
+
+impl FromStr for RecoveryTarget {
+    type Err = Error;
+
+    /// Parses a hex encoded fingerprint, a root or account xpub with any SLIP-132 version, or an address.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if let Ok(fingerprint) = Fingerprint::from_str(s) {
+            return Ok(RecoveryTarget::Fingerprint(fingerprint));
+        }
+        if let Ok(address) = Address::from_str(s) {
+            return match address.address_type() {
+                Some(AddressType::P2pkh) | Some(AddressType::P2sh) | Some(AddressType::P2wpkh) => {
+                    Ok(RecoveryTarget::Address(address))
+                }
+                _ => Err(Error::BadRecoveryTarget),
+            };
+        }
+
+        // Private keys are no targets, their seed would be known already
+        let xpub = match ExtendedKey::from_str(s) {
+            Ok(ExtendedKey::Public(xpub)) => xpub,
+            _ => return Err(Error::BadRecoveryTarget),
+        };
+
+        let path = match xpub.depth {
+            0 => DerivationPath::from(vec![]),
+            3 => key_version(s)
+                .and_then(|version| ScriptType::from_version(&version))
+                .and_then(|script_type| script_type.coin_path(xpub.network))
+                .map_err(|_| Error::BadRecoveryTarget)?
+                .child(xpub.child_number),
+            _ => return Err(Error::BadRecoveryTarget),
+        };
+
+        Ok(RecoveryTarget::Xpub(xpub, path))
+    }
+}
+
+impl RecoveryTarget {
+    /// Returns the network of the keys to compare with `self`.
+    pub(crate) fn network(&self) -> Network {
+        match self {
+            RecoveryTarget::Fingerprint(_) => Network::Bitcoin,
+            RecoveryTarget::Xpub(xpub, _) => xpub.network,
+            RecoveryTarget::Address(address) => address.network,
+        }
+    }
+
+    /// Returns `true` if `root` belongs to the seed `self` describes.
+    pub(crate) fn matches(
+        &self,
+        secp: &Secp256k1<SignOnly>,
+        root: &ExtendedPrivKey,
+    ) -> Result<bool, Error> {
+        match self {
+            RecoveryTarget::Fingerprint(fingerprint) => Ok(root.fingerprint(secp) == *fingerprint),
+            RecoveryTarget::Xpub(xpub, path) => {
+                let derived = ExtendedPubKey::from_private(secp, &root.derive_priv(secp, path)?);
+                Ok(derived.public_key == xpub.public_key && derived.chain_code == xpub.chain_code)
+            }
+            RecoveryTarget::Address(address) => {
+                let purpose = match address.address_type() {
+                    Some(AddressType::P2pkh) => 44,
+                    Some(AddressType::P2sh) => 49,
+                    _ => 84,
+                };
+                let coin_type = if address.network == Network::Bitcoin {
+                    0
+                } else {
+                    1
+                };
+                let receive_path = DerivationPath::from(vec![
+                    ChildNumber::from_hardened_idx(purpose)?,
+                    ChildNumber::from_hardened_idx(coin_type)?,
+                    ChildNumber::from_hardened_idx(0)?,
+                    ChildNumber::from_normal_idx(0)?,
+                ]);
+                let receive = root.derive_priv(secp, &receive_path)?;
+
+                for i in 0..ADDRESS_GAP {
+                    let child = receive.derive_priv(secp, &[ChildNumber::from_normal_idx(i)?])?;
+                    let public_key = ExtendedPubKey::from_private(secp, &child).public_key;
+                    let derived = match purpose {
+                        44 => Address::p2pkh(&public_key, address.network),
+                        49 => Address::p2shwpkh(&public_key, address.network)
+                            .map_err(|_| Error::BadRecoveryTarget)?,
+                        _ => Address::p2wpkh(&public_key, address.network)
+                            .map_err(|_| Error::BadRecoveryTarget)?,
+                    };
+                    if derived.script_pubkey() == address.script_pubkey() {
+                        return Ok(true);
+                    }
+                }
+
+                Ok(false)
+            }
+        }
+    }
+}
+
+/// Candidate words for every position of a damaged seed.
+struct Template {
+    candidates: Vec<Vec<u16>>,
+    count: u64,
+}
+
+impl Template {
+    fn new(candidates: Vec<Vec<u16>>) -> Option<Self> {
+        let count = candidates
+            .iter()
+            .try_fold(1u64, |count, words| count.checked_mul(words.len() as u64))?;
+
+        Some(Template { candidates, count })
+    }
+
+    /// Returns the word indexes of the `n`th combination of candidates.
+    fn indexes(&self, mut n: u64) -> Vec<u16> {
+        let mut indexes = vec![0u16; self.candidates.len()];
+        for (i, words) in self.candidates.iter().enumerate().rev() {
+            let len = words.len() as u64;
+            indexes[i] = words[(n % len) as usize];
+            n /= len;
+        }
+
+        indexes
+    }
+}
+
+/// Recovers a `damaged_seed` with unknown words written as `?`, misspelled words and, if `swaps` is `true`, two swapped neighbouring words.
+/// Misspelled words are replaced by all words with a small edit distance or the same first 4 letters.
+/// The words are in `language` or in the language which knows most of them if `language` is `None`.
+/// If a `target` is given, only candidates whose keys match it with the bip39 `passphrase` are returned.
+/// Candidates are checked with `threads` threads, or with one thread per CPU if `threads` is 0.
+/// `progress` is regularly called with the number of checked and total candidates.
+/// Fails with [Error::TooManyCandidates] for more than 2^32 candidates with a `target` or 2^22 without.
+/// Returns all checksum valid candidates that match `target`.
+pub fn recover_seed<S, F>(
+    damaged_seed: S,
+    language: Option<Language>,
+    passphrase: Option<&str>,
+    target: Option<&RecoveryTarget>,
+    swaps: bool,
+    threads: usize,
+    progress: F,
+) -> Result<Vec<Mnemonic>, Error>
+where
+    S: AsRef<str>,
+    F: Fn(u64, u64) + Sync,
+{
+    let words = normalize_words(damaged_seed.as_ref());
+    crate::WordCount::from_str(&words.len().to_string())?;
+    let language = language.unwrap_or_else(|| guess_language(&words));
+
+    let candidates: Vec<Vec<u16>> = words
+        .iter()
+        .map(|word| word_candidates(word, language))
+        .collect();
+    let mut templates = vec![candidates.clone()];
+    if swaps {
+        for i in 0..candidates.len() - 1 {
+            if candidates[i] != candidates[i + 1] {
+                let mut swapped = candidates.clone();
+                swapped.swap(i, i + 1);
+                templates.push(swapped);
+            }
+        }
+    }
+    let templates = templates
+        .into_iter()
+        .map(Template::new)
+        .collect::<Option<Vec<Template>>>()
+        .ok_or(Error::TooManyCandidates)?;
+    let total = templates
+        .iter()
+        .try_fold(0u64, |total, template| total.checked_add(template.count))
+        .ok_or(Error::TooManyCandidates)?;
+    check_candidate_count(total, target)?;
+
+    let threads = match threads {
+        0 => thread::available_parallelism().map_or(1, |threads| threads.get()),
+        threads => threads,
+    };
+    let next = AtomicU64::new(0);
+    let checked = AtomicU64::new(0);
+    let found = Mutex::new(Vec::new());
+
+    thread::scope(|scope| {
+        for _ in 0..threads {
+            scope.spawn(|| {
+                let secp = Secp256k1::signing_only();
+                loop {
+                    let start = next.fetch_add(CHUNK_SIZE, Ordering::Relaxed);
+                    if start >= total {
+                        break;
+                    }
+                    let end = total.min(start + CHUNK_SIZE);
+
+                    for n in start..end {
+                        if let Some(mnemonic) = candidate(&templates, n, language) {
+                            if is_target(&secp, &mnemonic, passphrase, target) {
+                                found.lock().unwrap().push(mnemonic);
+                            }
+                        }
+                    }
+                    let checked = checked.fetch_add(end - start, Ordering::Relaxed) + end - start;
+                    progress(checked, total);
+                }
+            });
+        }
+    });
+
+    let mut found = found.into_inner().unwrap();
+    found.sort_by_cached_key(|mnemonic| mnemonic.to_string());
+    found.dedup();
+
+    Ok(found)
+}
+
+/// Fails if `total` candidates exceed the maximum for `target`.
+fn check_candidate_count(total: u64, target: Option<&RecoveryTarget>) -> Result<(), Error> {
+    let max = match target {
+        Some(_) => MAX_CANDIDATES,
+        None => MAX_CANDIDATES_WITHOUT_TARGET,
+    };
+    if total > max {
+        return Err(Error::TooManyCandidates);
+    }
+
+    Ok(())
+}
+
+/// Returns the `n`th candidate of all `templates` if its checksum is valid.
+fn candidate(templates: &[Template], mut n: u64, language: Language) -> Option<Mnemonic> {
+    for template in templates {
+        if n < template.count {
+            let entropy = entropy_from_indexes(&template.indexes(n))?;
+            return Mnemonic::from_entropy_in(language, &entropy).ok();
+        }
+        n -= template.count;
+    }
+
+    None
+}
+
+/// Returns `true` if there is no `target` or the keys of `mnemonic` match it.
+fn is_target(
+    secp: &Secp256k1<SignOnly>,
+    mnemonic: &Mnemonic,
+    passphrase: Option<&str>,
+    target: Option<&RecoveryTarget>,
+) -> bool {
+    let target = match target {
+        Some(target) => target,
+        None => return true,
+    };
+    let seed = mnemonic.to_seed(passphrase.unwrap_or(""));
+
+    // Derivation only fails for invalid keys, which are no match either
+    ExtendedPrivKey::new_master(target.network(), &seed)
+        .map_err(Error::from)
+        .and_then(|root| target.matches(secp, &root))
+        .unwrap_or(false)
+}
+
+/// Returns the entropy of the words with `indexes` or `None` if their checksum is invalid.
+fn entropy_from_indexes(indexes: &[u16]) -> Option<Vec<u8>> {
+    let bit_count = indexes.len() * 11;
+    let checksum_bits = bit_count / 33;
+    let entropy_bits = bit_count - checksum_bits;
+
+    let mut bits = vec![0u8; bit_count.div_ceil(8)];
+    for (i, index) in indexes.iter().enumerate() {
+        for bit in 0..11 {
+            if index & (1 << (10 - bit)) != 0 {
+                let position = i * 11 + bit;
+                bits[position / 8] |= 1 << (7 - position % 8);
+            }
+        }
+    }
+
+    let entropy = bits[..entropy_bits / 8].to_vec();
+    let hash = sha256::Hash::hash(&entropy);
+    let checksum = bits[entropy_bits / 8];
+    let mask = !(0xffu8 >> checksum_bits);
+
+    if hash[0] & mask == checksum & mask {
+        Some(entropy)
+    } else {
+        None
+    }
+}
+
+/// Returns the NFKD normalized and lowercase words of `seed`.
+pub(crate) fn normalize_words(seed: &str) -> Vec<String> {
+    seed.split_whitespace()
+        .map(|word| word.nfkd().collect::<String>().to_lowercase())
+        .collect()
+}
+
+/// Guesses the language of damaged `words` as the one whose wordlist contains the most of them.
+pub(crate) fn guess_language(words: &[String]) -> Language {
+    let mut best = (Language::English, 0);
+    for language in Language::ALL.iter() {
+        let known = words
+            .iter()
+            .filter(|word| language.find_word(word).is_some())
+            .count();
+        if known > best.1 {
+            best = (*language, known);
+        }
+    }
+
+    best.0
+}
+
+/// Returns the indexes of all words `word` could be in `language`.
+/// Unknown words and misspelled words without similar words can be any word.
+fn word_candidates(word: &str, language: Language) -> Vec<u16> {
+    if word == UNKNOWN_WORD {
+        return (0..WORDLIST_SIZE).collect();
+    }
+    if let Some(index) = language.find_word(word) {
+        return vec![index];
+    }
+
+    let prefix: String = word.chars().take(PREFIX_LENGTH).collect();
+    let candidates: Vec<u16> = language
+        .word_list()
+        .iter()
+        .enumerate()
+        .filter(|(_, candidate)| {
+            (prefix.chars().count() == PREFIX_LENGTH && candidate.starts_with(&prefix))
+                || edit_distance(word, candidate) <= MAX_EDIT_DISTANCE
+        })
+        .map(|(i, _)| i as u16)
+        .collect();
+
+    if candidates.is_empty() {
+        (0..WORDLIST_SIZE).collect()
+    } else {
+        candidates
+    }
+}
+
+/// Returns the Levenshtein distance of `a` and `b`.
+pub(crate) fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+
+    for (i, char_a) in a.chars().enumerate() {
+        let mut current = vec![i + 1; b.len() + 1];
+        for (j, char_b) in b.iter().enumerate() {
+            let substitution = previous[j] + if char_a == *char_b { 0 } else { 1 };
+            current[j + 1] = substitution.min(previous[j + 1] + 1).min(current[j] + 1);
+        }
+        previous = current;
+    }
+
+    previous[b.len()]
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+    use std::sync::atomic::{AtomicU64, Ordering};
+
+    use bip39::{Language, Mnemonic};
+    use bitcoin::util::bip32::{DerivationPath, Fingerprint};
+    use bitcoin::Network;
+
+    use crate::recover::{
+        check_candidate_count, edit_distance, recover_seed, RecoveryTarget, MAX_CANDIDATES,
+        MAX_CANDIDATES_WITHOUT_TARGET,
+    };
+    use crate::{Error, ExtendedKey};
+
+    const SEED: &str =
+        "tourist correct mango profit mom embody move thought deputy trophy excuse torch";
+
+    #[test]
+    fn edit_distance_counts_edits() {
+        assert_eq!(edit_distance("abandon", "abandon"), 0);
+        assert_eq!(edit_distance("abandn", "abandon"), 1);
+        assert_eq!(edit_distance("abadnon", "abandon"), 2);
+        assert_eq!(edit_distance("", "zoo"), 3);
+    }
+
+    #[test]
+    fn recover_seed_recovers_unknown_words() {
+        let damaged = SEED.replace("mango", "?");
+        let progress = AtomicU64::new(0);
+        let result = recover_seed(&damaged, None, None, None, false, 2, |checked, total| {
+            assert_eq!(total, 2048);
+            progress.fetch_max(checked, Ordering::Relaxed);
+        })
+        .unwrap();
+
+        assert_eq!(progress.load(Ordering::Relaxed), 2048);
+        assert!(result.len() > 1);
+        assert!(result.contains(&Mnemonic::parse(SEED).unwrap()));
+        assert!(result
+            .iter()
+            .all(|mnemonic| mnemonic.to_string().starts_with("tourist correct ")));
+    }
+
+    #[test]
+    fn recover_seed_recovers_misspelled_and_swapped_words() {
+        let expected = Mnemonic::parse(SEED).unwrap();
+
+        let damaged = SEED.replace("profit", "prophit").replace("deputy", "dputy");
+        let result = recover_seed(&damaged, None, None, None, false, 0, |_, _| ()).unwrap();
+        assert!(result.contains(&expected));
+
+        let damaged = SEED.replace("mom embody", "embody mom");
+        let result = recover_seed(&damaged, None, None, None, true, 0, |_, _| ()).unwrap();
+        assert!(result.contains(&expected));
+        let result = recover_seed(&damaged, None, None, None, false, 0, |_, _| ()).unwrap();
+        assert!(result.is_empty());
+    }
+
+    #[test]
+    fn recover_seed_narrows_by_target() {
+        let damaged = SEED.replace("mango", "?");
+        let expected = vec![Mnemonic::parse(SEED).unwrap()];
+
+        let fingerprint = crate::derive_fingerprint(SEED, Some("TREZOR")).unwrap();
+        let target = RecoveryTarget::Fingerprint(fingerprint);
+        let result = recover_seed(
+            &damaged,
+            None,
+            Some("TREZOR"),
+            Some(&target),
+            false,
+            0,
+            |_, _| (),
+        )
+        .unwrap();
+        assert_eq!(result, expected);
+
+        // First receive address at m/84'/0'/0'/0/0
+        let abandon = "abandon ".repeat(11);
+        let damaged = format!("{}?", abandon);
+        let target =
+            RecoveryTarget::from_str("bc1qcr8te4kr609gcawutmrza0j4xv80jy8z306fyu").unwrap();
+        let result =
+            recover_seed(&damaged, None, None, Some(&target), false, 0, |_, _| ()).unwrap();
+        assert_eq!(result.len(), 1);
+        assert_eq!(result[0].to_string(), format!("{}about", abandon));
+
+        // Account zpub at m/84'/0'/0'
+        let target = RecoveryTarget::from_str("zpub6rFR7y4Q2AijBEqTUquhVz398htDFrtymD9xYYfG1m4wAcvPhXNfE3EfH1r1ADqtfSdVCToUG868RvUUkgDKf31mGDtKsAYz2oz2AGutZYs").unwrap();
+        let result =
+            recover_seed(&damaged, None, None, Some(&target), false, 0, |_, _| ()).unwrap();
+        assert_eq!(result.len(), 1);
+        assert_eq!(result[0].to_string(), format!("{}about", abandon));
+    }
+
+    #[test]
+    fn recover_seed_limits_candidates() {
+        let target = RecoveryTarget::from_str("73c5da0a").unwrap();
+        assert_eq!(check_candidate_count(MAX_CANDIDATES, Some(&target)), Ok(()));
+        assert_eq!(
+            check_candidate_count(MAX_CANDIDATES + 1, Some(&target)),
+            Err(Error::TooManyCandidates)
+        );
+        assert_eq!(
+            check_candidate_count(MAX_CANDIDATES_WITHOUT_TARGET, None),
+            Ok(())
+        );
+        assert_eq!(
+            check_candidate_count(MAX_CANDIDATES_WITHOUT_TARGET + 1, None),
+            Err(Error::TooManyCandidates)
+        );
+
+        // Three unknown words have 2^33 candidates
+        let damaged = SEED
+            .replace("mango", "?")
+            .replace("profit", "?")
+            .replace("deputy", "?");
+        let result = recover_seed(&damaged, None, None, Some(&target), false, 0, |_, _| ());
+        assert_eq!(result, Err(Error::TooManyCandidates));
+        let result = recover_seed(&damaged, None, None, None, false, 0, |_, _| ());
+        assert_eq!(result, Err(Error::TooManyCandidates));
+    }
+
+    #[test]
+    fn recover_seed_returns_err_when_input_invalid() {
+        let result = recover_seed("abandon ?", None, None, None, false, 0, |_, _| ());
+        assert_eq!(result, Err(Error::BadWordCount));
+
+        let damaged = "? ".repeat(12);
+        let result = recover_seed(
+            &damaged,
+            Some(Language::English),
+            None,
+            None,
+            true,
+            0,
+            |_, _| (),
+        );
+        assert_eq!(result, Err(Error::TooManyCandidates));
+
+        let target = RecoveryTarget::from_str("not a target");
+        assert_eq!(target, Err(Error::BadRecoveryTarget));
+
+        // Private keys are rejected
+        let target = RecoveryTarget::from_str("zprvAWgYBBk7JR8Gjrh4UJQ2uJdG1r3WNRRfURiABBE3RvMXYSrRJL62XuezvGdPvG6GFBZduosCc1YP5wixPox7zhZLfiUm8aunE96BBa4Kei5");
+        assert_eq!(target, Err(Error::BadRecoveryTarget));
+
+        // Testnet account xpubs are derived at coin type 1
+        let vpub = "vpub5Y6cjg78GGuNLsaPhmYsiw4gYX3HoQiRBiSwDaBXKUafCt9bNwWQiitDk5VZ5BVxYnQdwoTyXSs2JHRPAgjAvtbBrf8ZhDYe2jWAqvZVnsc";
+        let xpub = ExtendedKey::from_str(vpub).unwrap().xpub();
+        let path = DerivationPath::from_str("m/84'/1'/0'").unwrap();
+        assert_eq!(xpub.network, Network::Testnet);
+        assert_eq!(
+            RecoveryTarget::from_str(vpub),
+            Ok(RecoveryTarget::Xpub(xpub, path))
+        );
+
+        let target = RecoveryTarget::from_str("73c5da0a").unwrap();
+        assert_eq!(
+            target,
+            RecoveryTarget::Fingerprint(Fingerprint::from_str("73c5da0a").unwrap())
+        );
+    }
+}
@@ -0,0 +1,247 @@
+//! Registry of labelled bip85 child seeds.
+use std::str::FromStr;
+
+use bip39::Language;
+use bitcoin::util::bip32::Fingerprint;
+use serde::{Deserialize, Serialize};
+
+use crate::{Error, WordCount};
+
+/// Bip85 child seed registered under a label.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RegistryEntry {
+    /// Label describing the purpose of the child seed.
+    pub label: String,
+    /// Bip85 index of the child seed.
+    pub index: u32,
+    /// Word count of the child seed.
+    pub word_count: WordCount,
+    /// Language of the child seed.
+    pub language: Language,
+}
+
+/// Registry of labelled bip85 child seeds of a parent seed identified by its master fingerprint.
+/// The registry contains no secrets and can be stored as JSON next to backups of the parent seed.
+/// Every label and every index is registered at most once.
+/// Only bip39 child seeds are tracked, so entries have no bip85 application and are derived at `m/83696968'/39'`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ChildRegistry {
+    fingerprint: Fingerprint,
+    entries: Vec<RegistryEntry>,
+}
+
+impl ChildRegistry {
+    /// Creates an empty registry for the parent seed with `fingerprint`.
+    pub fn new(fingerprint: Fingerprint) -> Self {
+        ChildRegistry {
+            fingerprint,
+            entries: vec![],
+        }
+    }
+
+    /// Returns the master fingerprint of the parent seed.
+    pub fn fingerprint(&self) -> Fingerprint {
+        self.fingerprint
+    }
+
+    /// Returns all registered child seeds.
+    pub fn entries(&self) -> &[RegistryEntry] {
+        &self.entries
+    }
+
+    /// Returns the child seed registered under `label` or `None` if `label` is not registered.
+    pub fn entry(&self, label: &str) -> Option<&RegistryEntry> {
+        self.entries.iter().find(|entry| entry.label == label)
+    }
+
+    /// Returns the lowest index which is not registered yet.
+    pub fn next_index(&self) -> u32 {
+        (0..)
+            .find(|index| self.entries.iter().all(|entry| entry.index != *index))
+            .unwrap_or(0)
+    }
+
+    /// Registers `entry`. Registering the same entry twice does nothing.
+    /// Fails if its label is registered with other values or its index is registered for another label.
+    pub fn register(&mut self, entry: RegistryEntry) -> Result<(), Error> {
+        for registered in self.entries.iter() {
+            if *registered == entry {
+                return Ok(());
+            }
+            if registered.label == entry.label || registered.index == entry.index {
+                return Err(Error::RegistryConflict);
+            }
+        }
+        self.entries.push(entry);
+
+        Ok(())
+    }
+
+    /// Parses a registry from `json` like it is returned by [ChildRegistry::to_json].
+    pub fn from_json(json: &str) -> Result<Self, Error> {
+        let raw: RawRegistry = serde_json::from_str(json).map_err(|_| Error::BadRegistry)?;
+        let fingerprint =
+            Fingerprint::from_str(&raw.fingerprint).map_err(|_| Error::BadRegistry)?;
+
+        let mut registry = ChildRegistry::new(fingerprint);
+        for child in raw.children {
+            registry.register(RegistryEntry {
+                label: child.label,
+                index: child.index,
+                word_count: WordCount::from_str(&child.words.to_string())?,
+                language: language_from_name(&child.language).ok_or(Error::BadRegistry)?,
+            })?;
+        }
+
+        Ok(registry)
+    }
+
+    /// Returns the registry as pretty printed JSON.
+    pub fn to_json(&self) -> String {
+        let raw = RawRegistry {
+            fingerprint: self.fingerprint.to_string(),
+            children: self
+                .entries
+                .iter()
+                .map(|entry| RawChild {
+                    label: entry.label.clone(),
+                    index: entry.index,
+                    words: entry.word_count.count(),
+                    language: language_name(entry.language).to_string(),
+                })
+                .collect(),
+        };
+
+        // Serializing plain strings and numbers can't fail
+        serde_json::to_string_pretty(&raw).unwrap_or_default()
+    }
+}
+
+/// JSON representation of [ChildRegistry].
+#[derive(Serialize, Deserialize)]
+struct RawRegistry {
+    fingerprint: String,
+    children: Vec<RawChild>,
+}
+
+/// JSON representation of [RegistryEntry].
+#[derive(Serialize, Deserialize)]
+struct RawChild {
+    label: String,
+    index: u32,
+    words: u8,
+    language: String,
+}
+
+/// Returns the name of `language` like it is used on the command line.
+fn language_name(language: Language) -> &'static str {
+    match language {
+        Language::English => "english",
+        Language::SimplifiedChinese => "chinese-simplified",
+        Language::TraditionalChinese => "chinese-traditional",
+        Language::Czech => "czech",
+        Language::French => "french",
+        Language::Italian => "italian",
+        Language::Japanese => "japanese",
+        Language::Korean => "korean",
+        Language::Portuguese => "portuguese",
+        Language::Spanish => "spanish",
+    }
+}
+
+/// Returns the language with `name` or `None` if there is no such language.
+fn language_from_name(name: &str) -> Option<Language> {
+    Language::ALL
+        .iter()
+        .copied()
+        .find(|language| language_name(*language) == name)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use bip39::Language;
+    use bitcoin::util::bip32::Fingerprint;
+
+    use crate::registry::{ChildRegistry, RegistryEntry};
+    use crate::{Error, WordCount};
+
+    fn entry(label: &str, index: u32) -> RegistryEntry {
+        RegistryEntry {
+            label: label.to_string(),
+            index,
+            word_count: WordCount::Words12,
+            language: Language::Japanese,
+        }
+    }
+
+    #[test]
+    fn register_refuses_reused_labels_and_indexes() {
+        let mut registry = ChildRegistry::new(Fingerprint::from_str("73c5da0a").unwrap());
+        assert_eq!(registry.next_index(), 0);
+        registry.register(entry("accounting", 0)).unwrap();
+        registry.register(entry("sales", 2)).unwrap();
+        assert_eq!(registry.register(entry("sales", 2)), Ok(()));
+        assert_eq!(registry.entries().len(), 2);
+        assert_eq!(registry.next_index(), 1);
+        assert_eq!(registry.entry("sales"), Some(&entry("sales", 2)));
+        assert_eq!(registry.entry("legal"), None);
+
+        assert_eq!(
+            registry.register(entry("legal", 2)),
+            Err(Error::RegistryConflict)
+        );
+        assert_eq!(
+            registry.register(entry("sales", 3)),
+            Err(Error::RegistryConflict)
+        );
+    }
+
+    #[test]
+    fn to_json_and_from_json_roundtrip() {
+        let mut registry = ChildRegistry::new(Fingerprint::from_str("73c5da0a").unwrap());
+        registry.register(entry("accounting", 3)).unwrap();
+        let json = registry.to_json();
+        assert!(json.contains("\"fingerprint\": \"73c5da0a\""));
+        assert!(!json.contains("application"));
+        assert!(json.contains("\"language\": \"japanese\""));
+        assert_eq!(ChildRegistry::from_json(&json), Ok(registry));
+    }
+
+    #[test]
+    fn from_json_fails_for_bad_registries() {
+        let child = r#"{"label": "a", "index": 0, "words": 12, "language": "english"}"#;
+        let registries = [
+            "".to_string(),
+            r#"{"fingerprint": "73c5da0a"}"#.to_string(),
+            r#"{"fingerprint": "xyz", "children": []}"#.to_string(),
+            format!(
+                r#"{{"fingerprint": "73c5da0a", "children": [{}]}}"#,
+                child.replace("\"index\": 0", "\"index\": -1")
+            ),
+            format!(
+                r#"{{"fingerprint": "73c5da0a", "children": [{}]}}"#,
+                child.replace("english", "klingon")
+            ),
+        ];
+        for registry in registries.iter() {
+            assert_eq!(
+                ChildRegistry::from_json(registry),
+                Err(Error::BadRegistry),
+                "{}",
+                registry
+            );
+        }
+
+        let json = format!(
+            r#"{{"fingerprint": "73c5da0a", "children": [{}, {}]}}"#,
+            child,
+            child.replace("\"a\"", "\"b\"")
+        );
+        assert_eq!(
+            ChildRegistry::from_json(&json),
+            Err(Error::RegistryConflict)
+        );
+    }
+}
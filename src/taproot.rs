@@ -0,0 +1,88 @@
+//! Taproot output keys of bip86 accounts.
+use bitcoin::hashes::{sha256, Hash, HashEngine};
+use bitcoin::secp256k1::schnorrsig::PublicKey;
+use bitcoin::secp256k1::{self, Secp256k1};
+use bitcoin::util::bip32::{self, ChildNumber, ExtendedPubKey};
+
+use crate::{Chain, Error};
+
+/// Tag of the tagged hash used to tweak internal keys.
+const TAP_TWEAK_TAG: &[u8] = b"TapTweak";
+
+/// Derives the tweaked x-only output keys of a bip86 account `xpub` with an index range of `[start, end)`.
+/// Keys are derived at `/0/index` for the receive and at `/1/index` for the change `chain`.
+/// Internal keys are tweaked without a script tree as specified by bip86.
+/// Returns a tuple of the index and its output key.
+pub fn derive_taproot_output_keys(
+    xpub: &ExtendedPubKey,
+    chain: Chain,
+    (start, end): (u32, u32),
+) -> Result<Vec<(u32, PublicKey)>, Error> {
+    let secp = Secp256k1::verification_only();
+    let chain_xpub = xpub.ckd_pub(&secp, ChildNumber::from_normal_idx(chain.index())?)?;
+
+    let mut result = vec![];
+    for index in start..end {
+        let child = chain_xpub.ckd_pub(&secp, ChildNumber::from_normal_idx(index)?)?;
+        result.push((index, output_key(&child.public_key.key)?));
+    }
+
+    Ok(result)
+}
+
+/// Returns the tweaked x-only output key of an `internal_key` without a script tree.
+pub(crate) fn output_key(internal_key: &secp256k1::PublicKey) -> Result<PublicKey, Error> {
+    let secp = Secp256k1::verification_only();
+    let mut key = PublicKey::from(*internal_key);
+    let tweak = tap_tweak(&key);
+    key.tweak_add_assign(&secp, &tweak)
+        .map_err(bip32::Error::Ecdsa)?;
+
+    Ok(key)
+}
+
+/// Returns the tagged hash `TapTweak` of an internal `key` without a script tree.
+fn tap_tweak(key: &PublicKey) -> [u8; 32] {
+    let tag = sha256::Hash::hash(TAP_TWEAK_TAG);
+    let mut engine = sha256::Hash::engine();
+    engine.input(&tag[..]);
+    engine.input(&tag[..]);
+    engine.input(&key.serialize());
+
+    sha256::Hash::from_engine(engine).into_inner()
+}
+
+#[cfg(test)]
+mod tests {
+    use bitcoin::Network;
+
+    use crate::taproot::derive_taproot_output_keys;
+    use crate::{derive_script_xpubs_from_seed, Chain, ScriptType};
+
+    #[test]
+    fn derive_taproot_output_keys_derives_bip86_keys() {
+        let seed = "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about";
+        let xpubs =
+            derive_script_xpubs_from_seed(seed, None, (0, 1), &ScriptType::Tr, Network::Bitcoin)
+                .unwrap();
+        let (_, xpub) = xpubs.first().unwrap();
+
+        let result = derive_taproot_output_keys(xpub, Chain::Receive, (0, 2)).unwrap();
+        assert_eq!(result.len(), 2);
+        assert_eq!(result[0].0, 0);
+        assert_eq!(
+            result[0].1.to_string(),
+            "a60869f0dbcf1dc659c9cecbaf8050135ea9e8cdc487053f1dc6880949dc684c"
+        );
+        assert_eq!(
+            result[1].1.to_string(),
+            "a82f29944d65b86ae6b5e5cc75e294ead6c59391a1edc5e016e3498c67fc7bbb"
+        );
+
+        let result = derive_taproot_output_keys(xpub, Chain::Change, (0, 1)).unwrap();
+        assert_eq!(
+            result[0].1.to_string(),
+            "882d74e5d0572d5a816cef0041a96b6c1de832f6f9676d9605c44d5e9a97d3dc"
+        );
+    }
+}
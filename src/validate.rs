@@ -0,0 +1,185 @@
+//! Detailed validation of seeds.
+use std::str::FromStr;
+
+use bip39::Language;
+use bitcoin::util::bip32::Fingerprint;
+
+use crate::recover::{edit_distance, guess_language, normalize_words};
+use crate::{derive_fingerprint, parse_seed_in, Error, WordCount};
+
+/// Maximum number of suggestions for an unknown word.
+const MAX_SUGGESTIONS: usize = 3;
+
+/// Word of a seed which is not in the wordlist of the seed's language.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnknownWord {
+    /// Position of the word in the seed, starting at 1.
+    pub position: usize,
+    /// The unknown word itself.
+    pub word: String,
+    /// Closest words of the wordlist.
+    pub suggestions: Vec<&'static str>,
+}
+
+/// Report about what is valid or wrong with a seed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ValidationReport {
+    /// Language of the seed, either as given or detected.
+    pub language: Language,
+    /// Number of words of the seed.
+    pub word_count: usize,
+    /// `true` if the word count is 12, 15, 18, 21 or 24.
+    pub word_count_valid: bool,
+    /// Words which are not in the wordlist.
+    pub unknown_words: Vec<UnknownWord>,
+    /// `true` if the checksum is valid or `None` if it can't be checked because of unknown words or a bad word count.
+    pub checksum_valid: Option<bool>,
+    /// Bits of entropy if the seed is valid.
+    pub entropy_bits: Option<usize>,
+    /// Root fingerprint if the seed is valid.
+    pub fingerprint: Option<Fingerprint>,
+}
+
+impl ValidationReport {
+    /// Returns `true` if the seed is valid.
+    pub fn is_valid(&self) -> bool {
+        self.checksum_valid == Some(true)
+    }
+}
+
+/// Validates `seed` and reports its unknown words with suggestions, checksum, word count, entropy and root fingerprint.
+/// The words of `seed` are in `language` or in the language which knows most of them if `language` is `None`.
+/// The root fingerprint is derived with the optional bip39 `passphrase`.
+pub fn validate_seed<S>(
+    seed: S,
+    language: Option<Language>,
+    passphrase: Option<&str>,
+) -> Result<ValidationReport, Error>
+where
+    S: AsRef<str>,
+{
+    let words = normalize_words(seed.as_ref());
+    let normalized_seed = words.join(" ");
+    let word_count_valid = WordCount::from_str(&words.len().to_string()).is_ok();
+
+    if let Ok(mnemonic) = parse_seed_in(&normalized_seed, language) {
+        let fingerprint = derive_fingerprint(&normalized_seed, passphrase)?;
+        return Ok(ValidationReport {
+            language: mnemonic.language(),
+            word_count: words.len(),
+            word_count_valid,
+            unknown_words: vec![],
+            checksum_valid: Some(true),
+            entropy_bits: Some(mnemonic.to_entropy().len() * 8),
+            fingerprint: Some(fingerprint),
+        });
+    }
+
+    let language = language.unwrap_or_else(|| guess_language(&words));
+    let unknown_words: Vec<UnknownWord> = words
+        .iter()
+        .enumerate()
+        .filter(|(_, word)| language.find_word(word).is_none())
+        .map(|(i, word)| UnknownWord {
+            position: i + 1,
+            word: word.clone(),
+            suggestions: suggestions(word, language),
+        })
+        .collect();
+
+    // Parsing only fails because of the checksum if all words are known and the word count is valid
+    let checksum_valid = if unknown_words.is_empty() && word_count_valid {
+        Some(false)
+    } else {
+        None
+    };
+
+    Ok(ValidationReport {
+        language,
+        word_count: words.len(),
+        word_count_valid,
+        unknown_words,
+        checksum_valid,
+        entropy_bits: None,
+        fingerprint: None,
+    })
+}
+
+/// Returns the words of `language`'s wordlist which are closest to `word`.
+/// Words with the same first letter are preferred if their distance is equal.
+fn suggestions(word: &str, language: Language) -> Vec<&'static str> {
+    let first_letter = word.chars().next().unwrap_or_default();
+    let mut candidates: Vec<(usize, &'static str)> = language
+        .word_list()
+        .iter()
+        .map(|candidate| (edit_distance(word, candidate), *candidate))
+        .collect();
+    candidates
+        .sort_by_key(|(distance, candidate)| (*distance, !candidate.starts_with(first_letter)));
+
+    candidates
+        .into_iter()
+        .take(MAX_SUGGESTIONS)
+        .map(|(_, candidate)| candidate)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use bip39::Language;
+    use bitcoin::util::bip32::Fingerprint;
+
+    use crate::validate::validate_seed;
+
+    #[test]
+    fn validate_seed_reports_valid_seed() {
+        let seed = "Abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about";
+        let result = validate_seed(seed, None, None).unwrap();
+
+        assert!(result.is_valid());
+        assert_eq!(result.language, Language::English);
+        assert_eq!(result.word_count, 12);
+        assert!(result.word_count_valid);
+        assert!(result.unknown_words.is_empty());
+        assert_eq!(result.entropy_bits, Some(128));
+        assert_eq!(
+            result.fingerprint,
+            Some(Fingerprint::from_str("73c5da0a").unwrap())
+        );
+    }
+
+    #[test]
+    fn validate_seed_reports_unknown_words() {
+        let seed =
+            "tourist correct mangoo profit mom embody move thought deputy trophy excuse trch";
+        let result = validate_seed(seed, None, None).unwrap();
+
+        assert!(!result.is_valid());
+        assert_eq!(result.language, Language::English);
+        assert_eq!(result.unknown_words.len(), 2);
+        assert_eq!(result.unknown_words[0].position, 3);
+        assert_eq!(result.unknown_words[0].word, "mangoo");
+        assert_eq!(result.unknown_words[0].suggestions[0], "mango");
+        assert_eq!(result.unknown_words[1].position, 12);
+        assert_eq!(result.unknown_words[1].suggestions[0], "torch");
+        assert_eq!(result.checksum_valid, None);
+        assert_eq!(result.fingerprint, None);
+    }
+
+    #[test]
+    fn validate_seed_reports_bad_checksum_and_word_count() {
+        let seed = "abandon ".repeat(12);
+        let result = validate_seed(&seed, None, None).unwrap();
+        assert!(result.unknown_words.is_empty());
+        assert_eq!(result.checksum_valid, Some(false));
+        assert_eq!(result.entropy_bits, None);
+
+        let seed = "abandon ".repeat(13);
+        let result = validate_seed(&seed, None, None).unwrap();
+        assert_eq!(result.word_count, 13);
+        assert!(!result.word_count_valid);
+        assert_eq!(result.checksum_valid, None);
+    }
+}
@@ -0,0 +1,154 @@
+use std::process::{Command, Output};
+
+const SEED: &str =
+    "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about";
+
+/// Runs the binary with `args`.
+fn run(args: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_seed-utils"))
+        .args(args)
+        .output()
+        .unwrap()
+}
+
+/// Runs the binary with `args` and returns its stdout or panics if it fails.
+fn stdout(args: &[&str]) -> String {
+    let output = run(args);
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    String::from_utf8(output.stdout).unwrap()
+}
+
+/// Runs the binary with `args` and returns its stderr or panics if it succeeds.
+fn stderr(args: &[&str]) -> String {
+    let output = run(args);
+    assert!(!output.status.success());
+    String::from_utf8(output.stderr).unwrap()
+}
+
+#[test]
+fn addresses_uses_network_of_keys() {
+    let vpub = "vpub5Y6cjg78GGuNLsaPhmYsiw4gYX3HoQiRBiSwDaBXKUafCt9bNwWQiitDk5VZ5BVxYnQdwoTyXSs2JHRPAgjAvtbBrf8ZhDYe2jWAqvZVnsc";
+
+    let result = stdout(&["addresses", vpub, "-n", "1"]);
+    assert!(result.contains("Address at m/0/0: tb1q6rz28mcfaxtmd6v789l9rrlrusdprr9pqcpvkl"));
+
+    // Testnets can't be told apart by versions
+    let result = stdout(&["addresses", vpub, "-n", "1", "--network", "regtest"]);
+    assert!(result.contains("Address at m/0/0: bcrt1q6rz28mcfaxtmd6v789l9rrlrusdprr9pz3cppk"));
+
+    let result = stderr(&["addresses", vpub, "--network", "bitcoin"]);
+    assert!(result.contains("doesn't match the network [testnet] of the key"));
+}
+
+#[test]
+fn addresses_derives_accounts_of_root_keys() {
+    let zprv = "zprvAWgYBBk7JR8Gjrh4UJQ2uJdG1r3WNRRfURiABBE3RvMXYSrRJL62XuezvGdPvG6GFBZduosCc1YP5wixPox7zhZLfiUm8aunE96BBa4Kei5";
+
+    let result = stdout(&["addresses", zprv, "-n", "1"]);
+    assert!(
+        result.contains("Address at m/84'/0'/0'/0/0: bc1qcr8te4kr609gcawutmrza0j4xv80jy8z306fyu")
+    );
+
+    let expected = stdout(&["addresses", SEED, "-n", "1", "-a", "5"]);
+    let result = stdout(&["addresses", zprv, "-n", "1", "-a", "5"]);
+    assert!(result.contains("m/84'/0'/5'/0/0"));
+    assert_eq!(result, expected);
+
+    let zpub = "zpub6jftahH18ngZxLmXaKw3GSZzZsszmt9WqedkyZdezFtWRFBZqsQH5hyUmb4pCEeZGmVfQuP5bedXTB8is6fTv19U1GQRyQUKQGUTzyHACMF";
+    let result = stderr(&["addresses", zpub]);
+    assert!(result.contains("Accounts can't be derived from root xpubs"));
+}
+
+#[test]
+fn child_hex_derives_from_seeds_without_network() {
+    let xprv = "xprv9s21ZrQH143K3GJpoapnV8SFfukcVBSfeCficPSGfubmSFDxo1kuHnLisriDvSnRRuL2Qrg5ggqHKNVpxR86QEC8w35uxmGoggxtQTPvfUu";
+
+    let result = stdout(&["child-hex", SEED, "-b", "16"]);
+    assert_eq!(result, "Index 0: 866a12e42e31a09aa8ca4f25a02e999e\n");
+    assert_eq!(stdout(&["child-hex", xprv, "-b", "16"]), result);
+}
+
+#[test]
+fn child_label_rejects_flags_which_differ_from_registry() {
+    let registry = std::env::temp_dir().join("seed-utils-cli-test-registry.json");
+    let _ = std::fs::remove_file(&registry);
+    let registry = registry.to_str().unwrap();
+    let child = ["child", SEED, "--registry", registry, "--label", "sales"];
+
+    let expected = stdout(&[&child[..], &["-w", "12", "-l", "czech"]].concat());
+    assert!(expected.starts_with("Index 0 (sales): "));
+    assert_eq!(stdout(&child), expected);
+    assert_eq!(
+        stdout(&[&child[..], &["-i", "0", "-w", "12"]].concat()),
+        expected
+    );
+
+    let result = stderr(&[&child[..], &["-i", "1"]].concat());
+    assert!(result.contains("Label [sales] is registered at index 0"));
+    let result = stderr(&[&child[..], &["-w", "24"]].concat());
+    assert!(result.contains("Label [sales] is registered with 12 words"));
+    let result = stderr(&[&child[..], &["-l", "english"]].concat());
+    assert!(result.contains("Label [sales] is registered in Czech"));
+
+    std::fs::remove_file(registry).unwrap();
+}
+
+#[test]
+fn dice_derives_rolls_of_seeds_and_keys() {
+    let xprv = "xprv9s21ZrQH143K3GJpoapnV8SFfukcVBSfeCficPSGfubmSFDxo1kuHnLisriDvSnRRuL2Qrg5ggqHKNVpxR86QEC8w35uxmGoggxtQTPvfUu";
+
+    assert_eq!(stdout(&["dice", SEED]), "Index 0: 0,0,3,4,1,0,2,3,2,4\n");
+
+    let args = ["-s", "100", "-r", "3", "-i", "1", "-n", "2"];
+    let result = stdout(&[&["dice", SEED][..], &args[..]].concat());
+    assert_eq!(result, "Index 1: 19,53,63\nIndex 2: 90,28,39\n");
+    assert_eq!(stdout(&[&["dice", xprv][..], &args[..]].concat()), result);
+
+    stderr(&["dice", SEED, "-s", "1"]);
+}
+
+#[test]
+fn passphrase_is_rejected_for_extended_keys() {
+    let xprv = "xprv9s21ZrQH143K3GJpoapnV8SFfukcVBSfeCficPSGfubmSFDxo1kuHnLisriDvSnRRuL2Qrg5ggqHKNVpxR86QEC8w35uxmGoggxtQTPvfUu";
+    let zpub = "zpub6rFR7y4Q2AijBEqTUquhVz398htDFrtymD9xYYfG1m4wAcvPhXNfE3EfH1r1ADqtfSdVCToUG868RvUUkgDKf31mGDtKsAYz2oz2AGutZYs";
+    let subcommands = [
+        vec!["addresses", zpub],
+        vec!["child", xprv],
+        vec!["child", xprv, "-i", "0/1"],
+        vec![
+            "child",
+            xprv,
+            "--label",
+            "sales",
+            "--registry",
+            "registry.json",
+        ],
+        vec!["child-hex", xprv],
+        vec!["dice", xprv],
+        vec!["find-child", xprv, "73c5da0a", "-c", "1"],
+        vec!["xpub", xprv],
+    ];
+
+    for args in subcommands.iter() {
+        let result = stderr(&[&args[..], &["-p", "TREZOR"]].concat());
+        assert!(result.contains("Passphrase can only be set for seeds"));
+    }
+
+    // Seeds still take a passphrase
+    stdout(&["child-hex", SEED, "-p", "TREZOR"]);
+}
+
+#[test]
+fn range_fits_in_hardened_indexes() {
+    let result = stdout(&["child-hex", SEED, "-b", "16", "-i", "2147483647"]);
+    assert!(result.starts_with("Index 2147483647: "));
+
+    let result = stderr(&["child-hex", SEED, "-i", "2147483647", "-n", "2"]);
+    assert!(result.contains("index + number can't be higher than 2^31"));
+    let result = stderr(&["child-hex", SEED, "-n", "4294967296"]);
+    assert!(result.contains("number must fit in a u32"));
+}